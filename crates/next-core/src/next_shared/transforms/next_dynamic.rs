@@ -53,6 +53,8 @@ impl CustomTransformer for NextJsDynamic {
             NextDynamicMode::Webpack,
             FileName::Real(ctx.file_path_str.into()).into(),
             None,
+            Default::default(),
+            ctx.comments.clone(),
         ));
 
         Ok(())
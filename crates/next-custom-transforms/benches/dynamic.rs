@@ -0,0 +1,145 @@
+use std::{fmt::Write, path::PathBuf, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use next_custom_transforms::transforms::dynamic::{next_dynamic_with_entries, NextDynamicMode};
+use swc_core::{
+    common::{
+        comments::NoopComments,
+        errors::{DiagnosticBuilder, Emitter, Handler, HANDLER},
+        FileName, FilePathMapping, SourceMap, GLOBALS,
+    },
+    ecma::{
+        ast::Module,
+        parser::{parse_file_as_module, EsSyntax, Syntax},
+    },
+};
+
+struct NoopEmitter;
+
+impl Emitter for NoopEmitter {
+    fn emit(&mut self, _: &DiagnosticBuilder<'_>) {}
+}
+
+fn parse(src: String) -> Module {
+    let cm = SourceMap::new(FilePathMapping::empty());
+    let fm = cm.new_source_file(FileName::Anon.into(), src);
+
+    parse_file_as_module(
+        &fm,
+        Syntax::Es(EsSyntax {
+            jsx: true,
+            ..Default::default()
+        }),
+        Default::default(),
+        None,
+        &mut vec![],
+    )
+    .unwrap()
+}
+
+fn inputs() -> Vec<(&'static str, String)> {
+    let mut calls = String::new();
+    for i in 0..100 {
+        writeln!(
+            calls,
+            "const Component{i} = dynamic(() => import('../components/component-{i}'), {{ ssr: {} \
+             }})",
+            i % 2 == 0
+        )
+        .unwrap();
+    }
+
+    vec![
+        (
+            "single-call",
+            "import dynamic from 'next/dynamic'\n\nconst Hello = dynamic(() => \
+             import('../components/hello'))\n"
+                .into(),
+        ),
+        (
+            "multiple-calls",
+            format!("import dynamic from 'next/dynamic'\n\n{calls}"),
+        ),
+        (
+            "no-dynamic",
+            "import Link from 'next/link'\n\nexport default function Page() { return <Link \
+             href=\"/\">Home</Link> }\n"
+                .into(),
+        ),
+    ]
+}
+
+/// (name, is_development, is_server_compiler, mode)
+fn modes() -> Vec<(&'static str, bool, bool, NextDynamicMode)> {
+    let turbopack = NextDynamicMode::Turbopack {
+        dynamic_transition_name: "next-dynamic".into(),
+        import_attribute_keys: Default::default(),
+        module_id_export: None,
+        transition_import_phase: Default::default(),
+    };
+
+    vec![
+        ("webpack-dev-client", true, false, NextDynamicMode::Webpack),
+        (
+            "webpack-prod-client",
+            false,
+            false,
+            NextDynamicMode::Webpack,
+        ),
+        ("webpack-server", false, true, NextDynamicMode::Webpack),
+        ("turbopack-dev-client", true, false, turbopack.clone()),
+        ("turbopack-prod-server", false, true, turbopack),
+    ]
+}
+
+fn bench_dynamic(c: &mut Criterion) {
+    let mut group = c.benchmark_group("next_dynamic");
+    group.warm_up_time(Duration::from_secs(1));
+    group.measurement_time(Duration::from_secs(3));
+
+    let handler = Handler::with_emitter(true, false, Box::new(NoopEmitter));
+
+    GLOBALS.set(&Default::default(), || {
+        HANDLER.set(&handler, || {
+            for (input_name, src) in inputs() {
+                let module = parse(src);
+
+                for (mode_name, is_development, is_server_compiler, mode) in modes() {
+                    group.bench_with_input(
+                        BenchmarkId::new(mode_name, input_name),
+                        &module,
+                        |b, module| {
+                            // The inputs are cloned outside of the measurement.
+                            b.iter_batched(
+                                || (module.clone(), mode.clone()),
+                                |(module, mode)| {
+                                    next_dynamic_with_entries(
+                                        is_development,
+                                        is_server_compiler,
+                                        false,
+                                        true,
+                                        mode,
+                                        FileName::Real(PathBuf::from(
+                                            "/some-project/src/some-file.js",
+                                        ))
+                                        .into(),
+                                        Some("/some-project/src".into()),
+                                        Default::default(),
+                                        NoopComments,
+                                        module,
+                                    )
+                                },
+                                BatchSize::SmallInput,
+                            )
+                        },
+                    );
+                }
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(dynamic_benches, bench_dynamic);
+criterion_main!(dynamic_benches);
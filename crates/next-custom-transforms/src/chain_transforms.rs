@@ -225,6 +225,8 @@ where
                 NextDynamicMode::Webpack,
                 file.name.clone(),
                 opts.pages_dir.clone().or_else(|| opts.app_dir.clone()),
+                Default::default(),
+                comments.clone(),
             ),
             Optional::new(
                 crate::transforms::page_config::page_config(opts.is_development, opts.is_page_file),
@@ -1,20 +1,36 @@
 use std::{
-    path::{Path, PathBuf},
-    sync::Arc,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
 };
 
+use hex::encode as hex_encode;
 use pathdiff::diff_paths;
+use rustc_hash::FxHashSet;
+use serde::Serialize;
+use serde_json::json;
+use sha1::{Digest, Sha1};
 use swc_core::{
-    common::{errors::HANDLER, FileName, Span, DUMMY_SP},
+    common::{
+        comments::{Comment, CommentKind, Comments},
+        errors::{DiagnosticBuilder, DiagnosticId, Level, HANDLER},
+        BytePos, FileName, Span, Spanned, DUMMY_SP,
+    },
     ecma::{
         ast::{
-            op, ArrayLit, ArrowExpr, BinExpr, BlockStmt, BlockStmtOrExpr, Bool, CallExpr, Callee,
-            Expr, ExprOrSpread, ExprStmt, Id, Ident, IdentName, ImportDecl, ImportDefaultSpecifier,
-            ImportNamedSpecifier, ImportSpecifier, KeyValueProp, Lit, ModuleDecl, ModuleItem,
-            ObjectLit, Pass, Prop, PropName, PropOrSpread, Stmt, Str, Tpl, UnaryExpr, UnaryOp,
+            op, ArrayLit, ArrowExpr, AssignExpr, AssignTarget, AwaitExpr, BinExpr, BlockStmt,
+            BlockStmtOrExpr, Bool, CallExpr, Callee, CondExpr, Decl, ExportDecl,
+            ExportNamedSpecifier, ExportSpecifier, Expr, ExprOrSpread, ExprStmt, FnExpr,
+            GetterProp, Id, Ident, IdentName, ImportDecl, ImportDefaultSpecifier,
+            ImportNamedSpecifier, ImportPhase, ImportSpecifier, KeyValueProp, Lit, MemberExpr,
+            MemberProp, MetaPropExpr, MetaPropKind, MethodProp, Module, ModuleDecl,
+            ModuleExportName, ModuleItem, NamedExport, NewExpr, ObjectLit, OptCall, ParenExpr,
+            Pass, Pat, Program, Prop, PropName, PropOrSpread, SeqExpr, SetterProp,
+            SimpleAssignTarget, Stmt, Str, Tpl, TsAsExpr, TsConstAssertion, TsNonNullExpr,
+            UnaryExpr, UnaryOp, UpdateExpr, VarDecl, VarDeclKind, VarDeclarator,
         },
-        utils::{private_ident, quote_ident, ExprFactory},
-        visit::{fold_pass, Fold, FoldWith},
+        atoms::Atom,
+        utils::{collect_decls, find_pat_ids, private_ident, ExprFactory},
+        visit::{fold_pass, Fold, FoldWith, Visit, VisitWith},
     },
     quote,
 };
@@ -24,7 +40,8 @@ use swc_core::{
 ///
 /// **NOTE** We do not use `NextDynamicMode::Turbopack` yet. It isn't compatible
 /// with current loadable manifest, which causes hydration errors.
-pub fn next_dynamic(
+#[allow(clippy::too_many_arguments)]
+pub fn next_dynamic<C>(
     is_development: bool,
     is_server_compiler: bool,
     is_react_server_layer: bool,
@@ -32,29 +49,490 @@ pub fn next_dynamic(
     mode: NextDynamicMode,
     filename: Arc<FileName>,
     pages_or_app_dir: Option<PathBuf>,
-) -> impl Pass {
-    fold_pass(NextDynamicPatcher {
+    config: Config,
+    comments: C,
+) -> impl Pass
+where
+    C: Comments,
+{
+    fold_pass(NextDynamicPatcher::new(
         is_development,
         is_server_compiler,
         is_react_server_layer,
         prefer_esm,
+        mode,
+        filename,
         pages_or_app_dir,
+        config,
+        comments,
+    ))
+}
+
+/// Same as [next_dynamic], but the project directory is given as a string, for
+/// embedders whose modules live in a virtual filesystem. Keys are computed by
+/// stripping `project_root` from the module's file name, without any path
+/// normalization.
+#[allow(clippy::too_many_arguments)]
+pub fn next_dynamic_with_project_root<C>(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    prefer_esm: bool,
+    mode: NextDynamicMode,
+    filename: Arc<FileName>,
+    project_root: String,
+    config: Config,
+    comments: C,
+) -> impl Pass
+where
+    C: Comments,
+{
+    let mut patcher = NextDynamicPatcher::new(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        prefer_esm,
+        mode,
+        filename,
+        None,
+        config,
+        comments,
+    );
+    patcher.project_root = Some(project_root);
+
+    fold_pass(patcher)
+}
+
+/// Same as [next_dynamic], but runs the transform on `module` directly and
+/// also returns metadata about every `dynamic()` call that was rewritten, so
+/// integrators don't have to recover the manifest keys from the output.
+#[allow(clippy::too_many_arguments)]
+pub fn next_dynamic_with_entries<C>(
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    prefer_esm: bool,
+    mode: NextDynamicMode,
+    filename: Arc<FileName>,
+    pages_or_app_dir: Option<PathBuf>,
+    config: Config,
+    comments: C,
+    module: Module,
+) -> DynamicTransformResult
+where
+    C: Comments,
+{
+    let mut patcher = NextDynamicPatcher::new(
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        prefer_esm,
+        mode,
         filename,
+        pages_or_app_dir,
+        config,
+        comments,
+    );
+    let module = module.fold_with(&mut patcher);
+
+    DynamicTransformResult {
+        module,
+        entries: patcher.entries,
+        reexports_dynamic: patcher.reexports_dynamic,
+        hashed_keys: patcher.hashed_keys,
+    }
+}
+
+/// Removes what [next_dynamic] adds to modules, for tools running other passes
+/// on the original code: the `loadableGenerated` option of `dynamic()` calls
+/// the module id imports generated in Turbopack mode and the exports of
+/// [Config::preload_exports]. `mode` and `config`
+/// must be the ones the module was transformed with. Loaders replaced with
+/// `require.resolveWeak` calls can't be restored.
+pub fn strip_loadable_generated(mode: NextDynamicMode, config: Config) -> impl Pass {
+    fold_pass(LoadableGeneratedStripper {
+        mode,
+        config,
         dynamic_bindings: vec![],
-        is_next_dynamic_first_arg: false,
-        dynamically_imported_specifier: None,
-        state: match mode {
-            NextDynamicMode::Webpack => NextDynamicPatcherState::Webpack,
-            NextDynamicMode::Turbopack {
-                dynamic_transition_name,
-            } => NextDynamicPatcherState::Turbopack {
-                dynamic_transition_name,
-                imports: vec![],
-            },
-        },
+        loaded_specifiers: vec![],
     })
 }
 
+#[derive(Debug, Clone)]
+pub struct DynamicTransformResult {
+    pub module: Module,
+    pub entries: Vec<DynamicEntry>,
+    /// Whether the module re-exports the default export of `next/dynamic` (or of
+    /// one of [Config::additional_specifiers]), e.g. `export { default } from
+    /// 'next/dynamic'`. Such a barrel module can be added to the additional
+    /// specifiers of the modules importing it.
+    pub reexports_dynamic: bool,
+    /// The original React Loadable Manifest key of each hashed key, when
+    /// [Config::hash_module_keys] is enabled.
+    pub hashed_keys: Vec<(String, String)>,
+}
+
+/// A `dynamic()` call that received a `loadableGenerated` property.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DynamicEntry {
+    /// The React Loadable Manifest key, when it is known at compile time. It
+    /// isn't for Webpack production client builds (the key is the module id
+    /// returned by `require.resolveWeak`) unless [Config::unified_keys] is
+    /// enabled, nor for Turbopack, where the id is injected by the bundler.
+    pub key: Option<String>,
+    /// The specifier of the dynamically imported module.
+    pub specifier: String,
+    pub layer: DynamicEntryLayer,
+    pub mode: DynamicEntryMode,
+    /// How the module id of the entry was generated, for debugging builds that
+    /// mix layers.
+    pub branch: DynamicEntryBranch,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DynamicEntryLayer {
+    Client,
+    /// The server compiler, outside of the React Server Components layer.
+    Ssr,
+    ReactServer,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DynamicEntryMode {
+    Webpack,
+    Turbopack,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum DynamicEntryBranch {
+    /// `"{currentModulePath} -> {relativeImportedModulePath}"`, in Webpack
+    /// development and server builds.
+    WebpackKey,
+    /// `require.resolveWeak(...)`, in Webpack production client builds.
+    WebpackResolveWeak,
+    /// The key returned by the dynamic transition, in Turbopack development
+    /// server builds.
+    TurbopackDevelopmentTransition,
+    /// The imported module id, in Turbopack development client builds.
+    TurbopackDevelopmentId,
+    /// The id returned by the dynamic transition, in Turbopack server builds.
+    TurbopackBuildTransition,
+    /// The imported module id, in Turbopack production client builds.
+    TurbopackBuildId,
+}
+
+/// Additional options for the `next_dynamic` transform, mostly intended for
+/// frameworks and forks that build on top of Next.js.
+#[derive(Clone, Default)]
+pub struct Config {
+    /// How a `dynamic()` call without an explicit `ssr` option is treated when
+    /// deciding whether the `require.resolveWeak` rewrite applies.
+    pub ssr_default: SsrDefault,
+    /// Don't report calls without arguments or with more than two arguments.
+    /// Extra arguments are left untouched, which is useful for wrappers of
+    /// `next/dynamic` that strip them later on.
+    pub skip_argument_count_validation: bool,
+    /// Whether calls without arguments or with more than two arguments are
+    /// reported as errors or warnings. They are left untouched either way.
+    pub argument_count_severity: ArgumentCountSeverity,
+    /// Emit a note when `dynamic` is imported from a package with a similar API
+    /// to `next/dynamic`, like `@loadable/component`. Those calls aren't
+    /// transformed.
+    pub report_look_alike_imports: bool,
+    /// Emit a note when `dynamic` is passed as an argument, e.g. `wrap(dynamic)`,
+    /// instead of being called.
+    pub report_dynamic_as_value: bool,
+    /// Warn for loaders importing a non-static specifier, e.g.
+    /// ``import(`../components/${name}`)``, whose module can't be preloaded.
+    pub report_non_static_specifiers: bool,
+    /// Maps the name of a `FileName::Custom` module (e.g. `virtual:entry`) to a
+    /// synthetic path relative to the project directory, so that virtual
+    /// modules also get stable manifest keys.
+    pub custom_filename_mapper: Option<Arc<FileNameMapper>>,
+    /// Rewrites the specifiers imported by loaders before they are used in keys
+    /// and imports, e.g. to canonicalize them. Returning `None` keeps the
+    /// specifier as is.
+    pub specifier_rewriter: Option<Arc<SpecifierRewriter>>,
+    /// Add a `webpackPrefetch` magic comment to the loader's `import()` of calls
+    /// passing a `prefetch: true` option, in Webpack mode.
+    pub prefetch_option: bool,
+    /// Report the warnings of this transform as errors, e.g. to fail CI builds.
+    pub treat_warnings_as_errors: bool,
+    /// Find the imported module of loaders calling a module-scope helper, like
+    /// `dynamic(() => load())` with `const load = () => import('./x')`. Only one
+    /// level of indirection is followed.
+    pub follow_loader_helpers: bool,
+    /// Nest the generated `loadableGenerated` property under this key, e.g.
+    /// `{ __next: { loadableGenerated: {...} } }`, for runtimes that read it
+    /// from a different place.
+    pub loadable_generated_namespace: Option<String>,
+    /// Only collect the entries of the `dynamic()` calls, without modifying the
+    /// module. Meant for tools that report what the transform would do.
+    pub dry_run: bool,
+    /// Emit notes for `ssr: true` options, which are redundant, and `ssr: false`
+    /// options, which should be reviewed ahead of changes to their semantics.
+    pub report_ssr_option_usage: bool,
+    /// Decides whether the loader of a `dynamic()` call is replaced with a
+    /// `require.resolveWeak` call. Defaults to
+    /// [ResolveWeakContext::default_policy].
+    pub resolve_weak_policy: Option<Arc<ResolveWeakPolicy>>,
+    /// The function called instead of `require.resolveWeak`, for bundlers
+    /// exposing it under a different name.
+    pub resolve_weak_callee: Option<String>,
+    /// Export a function importing each dynamically imported module, like
+    /// `export const __preload_hello = () => import('../components/hello')`, so
+    /// that frameworks can load the modules of a route ahead of time. Only in
+    /// Webpack mode, and not for the loaders replaced with
+    /// `require.resolveWeak`, whose modules must stay out of the bundle. Names
+    /// taken by the module get a `_1`, `_2`, ... suffix.
+    pub preload_exports: bool,
+    /// Modules whose default export is `next/dynamic`, like internal barrels
+    /// doing `export { default } from 'next/dynamic'`. Calls to their default
+    /// import are transformed too. The set is shared, so that the configs of
+    /// many modules can be cloned cheaply, e.g. across worker threads.
+    pub additional_specifiers: Arc<FxHashSet<Atom>>,
+    /// Replace the `{currentModulePath} -> {relativeImportedModulePath}` keys of
+    /// Webpack development and server builds with a hash of them, so that source
+    /// paths don't end up in client bundles. The mapping to the original keys is
+    /// returned in [DynamicTransformResult::hashed_keys], and collected in
+    /// [Config::hashed_keys_buffer] if set.
+    pub hash_module_keys: bool,
+    /// Collects the hashed keys of [Config::hash_module_keys] with their original
+    /// key, for embedders running the [next_dynamic] pass, which doesn't return
+    /// them. The entries of every module transformed with the config are appended.
+    pub hashed_keys_buffer: Option<Arc<HashedKeysBuffer>>,
+    /// Warn once per module when `pages_or_app_dir` is missing, in which case
+    /// the `{currentModulePath} -> {relativeImportedModulePath}` keys of Webpack
+    /// builds (and of [Config::hybrid_module_ids]) contain absolute paths that
+    /// are unlikely to match the manifest. The module ids of Turbopack builds
+    /// don't depend on it, so there is no warning for them.
+    pub warn_missing_project_dir: bool,
+    /// Add the resolved `ssr` option of each call to its `loadableGenerated`
+    /// object, e.g. `loadableGenerated: { modules: [...], ssr: false }`, for
+    /// runtimes that read it from there.
+    pub loadable_generated_ssr: bool,
+    /// Directories the keys are made relative to instead of the project
+    /// directory, e.g. `app` and `app/(group)`. The closest one containing the
+    /// current module is used.
+    pub base_dirs: Vec<PathBuf>,
+    /// Prefix the specifiers that aren't relative nor absolute with `./` in the
+    /// keys of Webpack development and server builds, for projects where
+    /// `components/hello` and `./components/hello` are the same module.
+    pub normalize_specifiers: bool,
+    /// Also add the `{currentModulePath} -> {relativeImportedModulePath}` keys
+    /// to the `modules` of Webpack production client builds, next to the
+    /// `webpack` closure, so that builds mixing both forms share comparable
+    /// keys.
+    pub unified_keys: bool,
+    /// Use a URL path for the current module in Webpack keys, e.g.
+    /// `/src/some-file.js -> ../components/hello`, for edge runtimes keying
+    /// the manifest by URL.
+    pub url_keys: bool,
+    /// Drop the `loading` option of `ssr: false` calls in the server compiler,
+    /// where it's never rendered, so that its imports aren't bundled there.
+    pub strip_server_loading: bool,
+    /// Wrap the module ids of `modules` in functions, e.g. `modules: [() =>
+    /// "src/some-file.js -> " + "../components/hello"]`, for runtimes that
+    /// only evaluate them when needed.
+    pub lazy_module_ids: bool,
+    /// Warn when a module of the `app` directory imports a module of the `pages`
+    /// directory, or the other way around, as their keys often don't resolve.
+    pub report_router_boundary_crossings: bool,
+    /// Receives a JSON line for each entry, with the file name, key, specifier,
+    /// mode and layer of the call, for build inspection tools.
+    pub entry_report_sink: Option<Arc<EntryReportSink>>,
+    /// Only validate the `dynamic()` calls and the specifiers of their loaders,
+    /// leaving them as they are, for test runners like Jest where the React
+    /// Loadable Manifest isn't available.
+    pub is_test: bool,
+    /// A hash of the current module's content. Its first 8 characters are
+    /// appended to the keys of Webpack development and server builds, e.g.
+    /// `src/some-file.js -> ../components/hello?v=1a2b3c4d`, for deployments
+    /// busting caches keyed by them.
+    pub module_hash: Option<String>,
+    /// Precede the module ids of Turbopack builds with the keys of Webpack
+    /// development builds in `modules`, e.g. `modules: ["src/some-file.js -> "
+    /// + "../components/hello", id]`, for hybrid runtimes reading both.
+    pub hybrid_module_ids: bool,
+    /// Replaces the `id_` prefix of the module id idents imported in Turbopack
+    /// mode, e.g. `__next_dyn_id_` for `__next_dyn_id_src_some_file_js`, so
+    /// that they are recognizable in bundles.
+    pub private_ident_prefix: Option<String>,
+    /// Collects the diagnostics of the transform instead of reporting them to
+    /// the handler, tagged with the current module's file name and their
+    /// level, for builds grouping diagnostics by file. Errors don't fail the
+    /// build then, the embedder has to report them.
+    pub diagnostics_buffer: Option<Arc<DiagnosticsBuffer>>,
+    /// Transform the calls of `const` aliases of `next/dynamic` too, like
+    /// `const d = dynamic`. Aliases of aliases aren't followed.
+    pub follow_const_aliases: bool,
+    /// Report the relative specifiers imported by loaders that this callback
+    /// can't resolve, e.g. because of a typo in the path.
+    pub specifier_resolver: Option<Arc<SpecifierResolver>>,
+    /// Lowercase the current module's path in the keys of Webpack development
+    /// and server builds, e.g. `src/components/hello.js -> ./World` for
+    /// `src/Components/Hello.js`, so that they match across case-insensitive
+    /// filesystems. The specifiers are kept as is.
+    pub lowercase_key_paths: bool,
+    /// Whether the query of specifiers like `../components/hello?raw` is kept in
+    /// the keys of Webpack development and server builds. The imports keep it
+    /// either way.
+    pub specifier_query: SpecifierQuery,
+    /// Emit the keys of Webpack development and server builds as a single
+    /// string, e.g. `modules: ["src/some-file.js -> ../components/hello"]`,
+    /// instead of a concatenation, for runtimes matching them statically.
+    pub static_keys: bool,
+    /// Emit a note for `next/dynamic` imports that aren't used in the module,
+    /// so that they can be removed.
+    pub report_unused_imports: bool,
+    /// Resolve the `.` and `..` segments of the current module's relative path
+    /// and use `/` separators, e.g. `app/page.js` for `./pages/../app/page.js`,
+    /// so that keys don't depend on how the path was spelled.
+    pub canonical_rel_paths: bool,
+    /// Called with the options object of each transformed call, including the
+    /// generated `loadableGenerated` property, e.g. to validate it.
+    pub options_inspector: Option<Arc<OptionsInspector>>,
+    /// Append the export selected by loaders like `() =>
+    /// import('../components/widget').then((mod) => mod.Widget)` to the keys of
+    /// Webpack development and server builds, e.g. `src/some-file.js ->
+    /// ../components/widget#Widget`, for manifests keyed by export.
+    pub export_name_keys: bool,
+    /// Only pass the generated options when this global is defined, e.g.
+    /// `typeof __NEXT_LOADABLE__ !== "undefined" ? { loadableGenerated: {...} } :
+    /// undefined`, for runtimes that may not support them. The original options
+    /// are passed otherwise.
+    pub loadable_generated_guard: Option<String>,
+    /// Also emit the first module id under the singular `module` key, e.g.
+    /// `loadableGenerated: { modules: [id], module: id }`, for older loadable
+    /// runtimes. With `hybrid_module_ids`, it's the first Turbopack module id,
+    /// not the Webpack key preceding it.
+    pub legacy_module_key: bool,
+    /// Emit a note for calls of functions returned by helpers wrapping
+    /// `next/dynamic`, like `withTracking(dynamic)(() => import('...'))`, which
+    /// aren't transformed.
+    pub report_wrapped_callees: bool,
+    /// Shorten the specifiers longer than this in the keys of Webpack
+    /// development and server builds, e.g. for generated modules, by replacing
+    /// their end with a hash of them. A warning is emitted for each of them.
+    pub max_specifier_length: Option<usize>,
+}
+
+impl Config {
+    fn is_dynamic_source(&self, src: &Atom) -> bool {
+        &**src == "next/dynamic" || self.additional_specifiers.contains(src)
+    }
+}
+
+pub type ResolveWeakPolicy = dyn Fn(&ResolveWeakContext) -> bool + Send + Sync;
+
+/// What is known about a `dynamic()` call when deciding whether its module
+/// should be decoupled from the current bundle with `require.resolveWeak`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ResolveWeakContext {
+    pub is_server_compiler: bool,
+    pub is_react_server_layer: bool,
+    pub prefer_esm: bool,
+    /// Whether the call passes `ssr: false`, or omits it with
+    /// [SsrDefault::Disabled].
+    pub has_ssr_false: bool,
+    pub mode: DynamicEntryMode,
+}
+
+impl ResolveWeakContext {
+    /// Applies to `ssr: false` calls in the SSR layer of the server compiler,
+    /// with Webpack.
+    pub fn default_policy(&self) -> bool {
+        self.has_ssr_false
+            && self.is_server_compiler
+            && !self.is_react_server_layer
+            // When it's not prefer to picking up ESM, as it's in the pages router, we don't need to do it as it doesn't need to enter the non-ssr module.
+            // Also transforming it to `require.resolveWeak` and with ESM import, like require.resolveWeak(esm asset) is not available as it's commonjs importing ESM.
+            && self.prefer_esm
+            // Only use `require.resolveWebpack` to decouple modules for webpack,
+            // turbopack doesn't need this
+            && self.mode == DynamicEntryMode::Webpack
+    }
+}
+
+/// Stable codes of the errors reported by the `next_dynamic` transform, for
+/// tools that handle some of them specifically.
+pub mod codes {
+    pub const NO_ARGUMENTS: &str = "NEXT_DYNAMIC_NO_ARGUMENTS";
+    pub const TOO_MANY_ARGUMENTS: &str = "NEXT_DYNAMIC_TOO_MANY_ARGUMENTS";
+    pub const INVALID_OPTIONS: &str = "NEXT_DYNAMIC_INVALID_OPTIONS";
+    pub const LOADER_PARAMS: &str = "NEXT_DYNAMIC_LOADER_PARAMS";
+    pub const STRING_ARGUMENT: &str = "NEXT_DYNAMIC_STRING_ARGUMENT";
+    pub const EMPTY_SPECIFIER: &str = "NEXT_DYNAMIC_EMPTY_SPECIFIER";
+    pub const UNRESOLVED_SPECIFIER: &str = "NEXT_DYNAMIC_UNRESOLVED_SPECIFIER";
+}
+
+pub type FileNameMapper = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+pub type EntryReportSink = dyn Fn(&str) + Send + Sync;
+
+pub type SpecifierRewriter = dyn Fn(&str) -> Option<String> + Send + Sync;
+
+/// Whether a relative specifier imported by the module with the given file name
+/// exists.
+pub type SpecifierResolver = dyn Fn(&FileName, &str) -> bool + Send + Sync;
+
+pub type OptionsInspector = dyn Fn(&ObjectLit) + Send + Sync;
+
+pub type DiagnosticsBuffer = Mutex<Vec<FileDiagnostic>>;
+
+/// `(hashed key, original key)` pairs collected in [Config::hashed_keys_buffer].
+pub type HashedKeysBuffer = Mutex<Vec<(String, String)>>;
+
+/// A diagnostic collected in [Config::diagnostics_buffer].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FileDiagnostic {
+    pub filename: Arc<FileName>,
+    pub span: Span,
+    /// `Level::Error` for the warnings reported as errors with
+    /// [Config::treat_warnings_as_errors].
+    pub level: Level,
+    /// One of the [codes], for errors that have one.
+    pub code: Option<String>,
+    pub message: String,
+}
+
+/// Packages with an API close enough to `next/dynamic` that importing them as
+/// `dynamic` is likely a mistake.
+const LOOK_ALIKE_PACKAGES: &[&str] = &[
+    "@loadable/component",
+    "react-loadable",
+    "loadable-components",
+];
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum SsrDefault {
+    /// A missing `ssr` option behaves like `ssr: true`, so the module is only
+    /// decoupled from the SSR bundle when `ssr: false` is explicit.
+    #[default]
+    Enabled,
+    /// A missing `ssr` option behaves like `ssr: false`.
+    Disabled,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ArgumentCountSeverity {
+    #[default]
+    Error,
+    /// E.g. for migration tools that fix the calls later on.
+    Warning,
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum SpecifierQuery {
+    #[default]
+    Preserve,
+    /// For manifests keyed by the imported module regardless of the loader
+    /// selected by the query.
+    Strip,
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum NextDynamicMode {
     /// In Webpack mode, each `dynamic()` call will generate a key composed
@@ -74,21 +552,83 @@ pub enum NextDynamicMode {
     /// * during build, each `dynamic()` call will import the module through the given transition,
     ///   which takes care of adding an entry to the manifest and returning an asset that exports
     ///   the entry's key.
-    Turbopack { dynamic_transition_name: String },
+    Turbopack {
+        dynamic_transition_name: String,
+        import_attribute_keys: ImportAttributeKeys,
+        /// The export imported to get the id of a module. Defaults to
+        /// `__turbopack_module_id__`.
+        module_id_export: Option<String>,
+        /// The phase of the imports going through `dynamic_transition_name`,
+        /// e.g. `ImportPhase::Defer` for builds evaluating them lazily.
+        transition_import_phase: ImportPhase,
+    },
+}
+
+/// The keys of the `with` clause of the imports generated in Turbopack mode, for
+/// bundlers that use different conventions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ImportAttributeKeys {
+    pub transition: String,
+    pub chunking_type: String,
+    /// Set to the `chunkGroup` option of `dynamic()` calls, if any.
+    pub chunk_group: String,
+}
+
+impl Default for ImportAttributeKeys {
+    fn default() -> Self {
+        ImportAttributeKeys {
+            transition: "transition".into(),
+            chunking_type: "chunking-type".into(),
+            chunk_group: "turbopack-chunk-group".into(),
+        }
+    }
 }
 
-#[derive(Debug)]
-struct NextDynamicPatcher {
+struct NextDynamicPatcher<C: Comments> {
     is_development: bool,
     is_server_compiler: bool,
     is_react_server_layer: bool,
     prefer_esm: bool,
     pages_or_app_dir: Option<PathBuf>,
+    /// Replaces `pages_or_app_dir` when the project directory is only known as
+    /// a string.
+    project_root: Option<String>,
     filename: Arc<FileName>,
+    config: Config,
+    comments: C,
     dynamic_bindings: Vec<Id>,
+    reassigned_bindings: Vec<Id>,
+    /// The bindings of [Config::follow_const_aliases], which are also part of
+    /// `dynamic_bindings`.
+    const_aliases: Vec<Id>,
+    /// The local bindings of the other imports, with the module they are
+    /// imported from.
+    imported_bindings: Vec<(Id, Atom)>,
+    /// Module-scope `const` arrow functions, with the specifier of the module
+    /// they import if they are simple enough to be followed.
+    loader_helpers: Vec<(Id, Option<(String, Span)>)>,
+    /// Module-scope `const` booleans, for `ssr` options given as a shorthand
+    /// property like `{ ssr }`.
+    module_booleans: Vec<(Id, bool)>,
+    /// Whether the module has a `"use no-loadable"` directive, in which case
+    /// `loadableGenerated` isn't added to its calls.
+    is_loadable_disabled: bool,
     is_next_dynamic_first_arg: bool,
-    dynamically_imported_specifier: Option<(String, Span)>,
+    dynamically_imported_specifiers: Vec<(String, Span)>,
+    /// The `with` attributes of the `import()` calls of loaders, e.g. `{ type:
+    /// 'worker' }`, which are kept on the imports generated in Turbopack mode.
+    import_attributes: Vec<(String, Vec<(String, String)>)>,
+    /// The modules to export a preload function for, see [Config::preload_exports].
+    preload_specifiers: Vec<String>,
+    /// The calls initializing a declarator with a `next-dynamic: client-only`
+    /// comment after its `=`, which is attached to that token rather than to
+    /// the call.
+    client_only_calls: Vec<Span>,
+    reexports_dynamic: bool,
+    hashed_keys: Vec<(String, String)>,
+    reported_missing_project_dir: bool,
     state: NextDynamicPatcherState,
+    entries: Vec<DynamicEntry>,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -99,6 +639,9 @@ enum NextDynamicPatcherState {
     #[allow(unused)]
     Turbopack {
         dynamic_transition_name: String,
+        import_attribute_keys: ImportAttributeKeys,
+        module_id_export: Atom,
+        transition_import_phase: ImportPhase,
         imports: Vec<TurbopackImport>,
     },
 }
@@ -124,104 +667,449 @@ enum TurbopackImport {
     },
 }
 
-impl Fold for NextDynamicPatcher {
+impl<C: Comments> Fold for NextDynamicPatcher<C> {
     fn fold_module_items(&mut self, mut items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        let mut reassigned = ReassignedIdents::default();
+        items.visit_with(&mut reassigned);
+        self.reassigned_bindings = reassigned.ids;
+
+        if self.config.follow_loader_helpers {
+            self.loader_helpers = collect_loader_helpers(&items);
+        }
+
+        self.module_booleans = collect_module_booleans(&items);
+        self.is_loadable_disabled = has_directive(&items, "use no-loadable");
+
         items = items.fold_children_with(self);
 
         self.maybe_add_dynamically_imported_specifier(&mut items);
+        self.maybe_add_preload_exports(&mut items);
+
+        if self.config.report_unused_imports {
+            self.report_unused_imports(&items);
+        }
 
         items
     }
 
+    fn fold_named_export(&mut self, export: NamedExport) -> NamedExport {
+        // export { default } from 'next/dynamic'
+        if let Some(src) = &export.src {
+            if self.is_dynamic_source(&src.value)
+                && export.specifiers.iter().any(|specifier| {
+                    matches!(
+                        specifier,
+                        ExportSpecifier::Named(ExportNamedSpecifier {
+                            orig: ModuleExportName::Ident(orig),
+                            ..
+                        }) if &*orig.sym == "default"
+                    )
+                })
+            {
+                self.reexports_dynamic = true;
+            }
+        }
+
+        export
+    }
+
     fn fold_import_decl(&mut self, decl: ImportDecl) -> ImportDecl {
         let ImportDecl {
             ref src,
             ref specifiers,
             ..
         } = decl;
-        if &src.value == "next/dynamic" {
+        if !self.is_dynamic_source(&src.value) {
+            self.imported_bindings
+                .extend(specifiers.iter().map(|specifier| {
+                    let local = match specifier {
+                        ImportSpecifier::Named(specifier) => &specifier.local,
+                        ImportSpecifier::Default(specifier) => &specifier.local,
+                        ImportSpecifier::Namespace(specifier) => &specifier.local,
+                    };
+                    (local.to_id(), src.value.clone())
+                }));
+        }
+        if self.is_dynamic_source(&src.value) {
             for specifier in specifiers {
                 if let ImportSpecifier::Default(default_specifier) = specifier {
                     self.dynamic_bindings.push(default_specifier.local.to_id());
                 }
             }
+        } else if self.config.report_look_alike_imports
+            && LOOK_ALIKE_PACKAGES.contains(&&*src.value)
+        {
+            for specifier in specifiers {
+                if let ImportSpecifier::Default(default_specifier) = specifier {
+                    // Only report imports named like Next's API, `loadable` from these packages
+                    // is most likely used on purpose.
+                    if &*default_specifier.local.sym == "dynamic" {
+                        self.emit_note(
+                            default_specifier.span,
+                            &format!(
+                                "`dynamic` is imported from '{}'. Did you mean to import it from \
+                                 'next/dynamic'?",
+                                src.value
+                            ),
+                        );
+                    }
+                }
+            }
         }
 
         decl
     }
 
+    fn fold_var_decl(&mut self, decl: VarDecl) -> VarDecl {
+        // const d = dynamic
+        if self.config.follow_const_aliases && decl.kind == VarDeclKind::Const {
+            for declarator in &decl.decls {
+                let (Pat::Ident(name), Some(init)) = (&declarator.name, &declarator.init) else {
+                    continue;
+                };
+                let Expr::Ident(target) = &**init else {
+                    continue;
+                };
+                let target = target.to_id();
+                if self.dynamic_bindings.contains(&target)
+                    && !self.const_aliases.contains(&target)
+                    && !self.reassigned_bindings.contains(&target)
+                {
+                    self.dynamic_bindings.push(name.id.to_id());
+                    self.const_aliases.push(name.id.to_id());
+                }
+            }
+        }
+
+        decl.fold_children_with(self)
+    }
+
+    fn fold_var_declarator(&mut self, decl: VarDeclarator) -> VarDeclarator {
+        // const dynamic = require('next/dynamic')
+        if let (Pat::Ident(name), Some(init)) = (&decl.name, &decl.init) {
+            if is_next_dynamic_require(init) {
+                self.dynamic_bindings.push(name.id.to_id());
+            }
+        }
+        // const Hello = // next-dynamic: client-only
+        //   dynamic(() => import('../components/hello'))
+        if let Some(Expr::Call(call)) = decl.init.as_deref().map(Expr::unwrap_parens) {
+            let is_client_only = (decl.name.span().hi.0..=call.span.lo.0).any(|pos| {
+                self.comments.with_trailing(BytePos(pos), |comments| {
+                    comments.iter().any(is_client_only_comment)
+                })
+            });
+            if is_client_only {
+                self.client_only_calls.push(call.span);
+            }
+        }
+
+        decl.fold_children_with(self)
+    }
+
+    // dynamic?.(() => import('../components/hello'))
+    fn fold_opt_call(&mut self, call: OptCall) -> OptCall {
+        let OptCall {
+            span,
+            ctxt,
+            callee,
+            args,
+            type_args,
+        } = call;
+        let CallExpr {
+            span,
+            ctxt,
+            callee,
+            args,
+            type_args,
+        } = self.fold_call_expr(CallExpr {
+            span,
+            ctxt,
+            callee: Callee::Expr(callee),
+            args,
+            type_args,
+        });
+        let Callee::Expr(callee) = callee else {
+            unreachable!("the callee of an optional call is an expression")
+        };
+
+        OptCall {
+            span,
+            ctxt,
+            callee,
+            args,
+            type_args,
+        }
+    }
+
     fn fold_call_expr(&mut self, expr: CallExpr) -> CallExpr {
         if self.is_next_dynamic_first_arg {
             if let Callee::Import(..) = &expr.callee {
-                match &*expr.args[0].expr {
-                    Expr::Lit(Lit::Str(Str { value, span, .. })) => {
-                        self.dynamically_imported_specifier = Some((value.to_string(), *span));
-                    }
-                    Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => {
-                        self.dynamically_imported_specifier =
-                            Some((quasis[0].raw.to_string(), quasis[0].span));
+                match static_specifier(&expr.args[0].expr) {
+                    Some(specifier) => {
+                        let specifier = self.rewrite_specifier(specifier);
+                        if specifier.0.is_empty() {
+                            // The key would end with ` -> `, which can't be resolved.
+                            self.emit_error(
+                                expr.span,
+                                "next/dynamic loaders can't import an empty specifier",
+                                codes::EMPTY_SPECIFIER,
+                            );
+                            return expr.fold_children_with(self);
+                        }
+                        if let Some(resolver) = &self.config.specifier_resolver {
+                            if (specifier.0.starts_with("./") || specifier.0.starts_with("../"))
+                                && !resolver(&self.filename, &specifier.0)
+                            {
+                                self.emit_error(
+                                    specifier.1,
+                                    &format!(
+                                        "next/dynamic can't resolve `{}` from this module",
+                                        specifier.0
+                                    ),
+                                    codes::UNRESOLVED_SPECIFIER,
+                                );
+                            }
+                        }
+                        let attributes = import_attributes(&expr);
+                        if !attributes.is_empty()
+                            && !self
+                                .import_attributes
+                                .iter()
+                                .any(|(s, _)| *s == specifier.0)
+                        {
+                            self.import_attributes
+                                .push((specifier.0.clone(), attributes));
+                        }
+                        if !self
+                            .dynamically_imported_specifiers
+                            .iter()
+                            .any(|(s, _)| *s == specifier.0)
+                        {
+                            self.dynamically_imported_specifiers.push(specifier);
+                        }
                     }
-                    _ => {}
+                    None if is_import_meta_url(&expr.args[0].expr) => self.emit_warning(
+                        expr.span,
+                        "next/dynamic can't add modules imported with `new URL(..., \
+                         import.meta.url)` to the React Loadable Manifest. Pass a string literal \
+                         to `import()` instead.",
+                    ),
+                    None if self.config.report_non_static_specifiers => self.emit_warning(
+                        expr.span,
+                        "next/dynamic can't preload modules imported with a non-static specifier. \
+                         Pass a string literal to `import()` instead.",
+                    ),
+                    None => {}
                 }
             }
             return expr.fold_children_with(self);
         }
         let mut expr = expr.fold_children_with(self);
+        if self.config.report_dynamic_as_value {
+            for arg in &expr.args {
+                if let Expr::Ident(ident) = &*arg.expr {
+                    if self.dynamic_bindings.contains(&ident.to_id()) {
+                        self.emit_note(
+                            ident.span,
+                            "`next/dynamic` is passed as a value. The modules loaded by the calls \
+                             of the function receiving it aren't added to the React Loadable \
+                             Manifest.",
+                        );
+                    }
+                }
+            }
+        }
+        if self.config.report_wrapped_callees {
+            self.report_wrapped_callee(&expr.callee);
+        }
         if let Callee::Expr(i) = &expr.callee {
-            if let Expr::Ident(identifier) = &**i {
-                if self.dynamic_bindings.contains(&identifier.to_id()) {
+            if let Some(identifier) = callee_ident(i) {
+                if self.dynamic_bindings.contains(&identifier.to_id())
+                    // The binding may not refer to `next/dynamic` anymore.
+                    && !self.reassigned_bindings.contains(&identifier.to_id())
+                {
                     if expr.args.is_empty() {
-                        HANDLER.with(|handler| {
-                            handler
-                                .struct_span_err(
-                                    identifier.span,
-                                    "next/dynamic requires at least one argument",
-                                )
-                                .emit()
-                        });
+                        if !self.config.skip_argument_count_validation {
+                            self.emit_argument_count_diagnostic(
+                                identifier.span,
+                                "next/dynamic requires at least one argument",
+                                codes::NO_ARGUMENTS,
+                            );
+                        }
                         return expr;
-                    } else if expr.args.len() > 2 {
-                        HANDLER.with(|handler| {
-                            handler
-                                .struct_span_err(
-                                    identifier.span,
-                                    "next/dynamic only accepts 2 arguments",
-                                )
-                                .emit()
-                        });
+                    } else if expr.args.len() > 2 && !self.config.skip_argument_count_validation {
+                        self.emit_argument_count_diagnostic(
+                            identifier.span,
+                            "next/dynamic only accepts 2 arguments",
+                            codes::TOO_MANY_ARGUMENTS,
+                        );
                         return expr;
                     }
-                    if expr.args.len() == 2 {
+                    if expr.args.len() >= 2 {
                         match &*expr.args[1].expr {
+                            // The call was already transformed, e.g. when the transform runs
+                            // twice on the same module. Its loader isn't visited, so no
+                            // Turbopack import is added for it again either.
+                            Expr::Object(options)
+                                if has_loadable_generated(
+                                    options,
+                                    self.config.loadable_generated_namespace.as_deref(),
+                                ) =>
+                            {
+                                return expr;
+                            }
                             Expr::Object(_) => {}
+                            // Guarded with `Config::loadable_generated_guard` by a previous run.
+                            Expr::Cond(CondExpr { cons, .. })
+                                if cons.as_object().is_some_and(|options| {
+                                    has_loadable_generated(
+                                        options,
+                                        self.config.loadable_generated_namespace.as_deref(),
+                                    )
+                                }) =>
+                            {
+                                return expr;
+                            }
+                            // dynamic(loader, cond ? { ssr: false } : {})
+                            Expr::Cond(_) => {
+                                self.emit_error(
+                                    identifier.span,
+                                    "next/dynamic options must be an object literal, not a \
+                                     conditional expression. Move the condition inside the \
+                                     object instead, e.g. `{ ssr: !cond }`.\nRead more: \
+                                     https://nextjs.org/docs/messages/invalid-dynamic-options-type",
+                                    codes::INVALID_OPTIONS,
+                                );
+                                return expr;
+                            }
                             _ => {
-                                HANDLER.with(|handler| {
-                          handler
-                              .struct_span_err(
-                                  identifier.span,
-                                  "next/dynamic options must be an object literal.\nRead more: https://nextjs.org/docs/messages/invalid-dynamic-options-type",
-                              )
-                              .emit();
-                      });
+                                self.emit_error(
+                                    identifier.span,
+                                    "next/dynamic options must be an object literal.\nRead more: \
+                                     https://nextjs.org/docs/messages/invalid-dynamic-options-type",
+                                    codes::INVALID_OPTIONS,
+                                );
                                 return expr;
                             }
                         }
                     }
 
+                    if let Expr::Lit(Lit::Str(Str { value, span, .. })) =
+                        expr.args[0].expr.unwrap_parens()
+                    {
+                        self.emit_error(
+                            *span,
+                            &format!(
+                                "next/dynamic expects a function loading the module, not its \
+                                 path. Use `dynamic(() => import('{value}'))` instead."
+                            ),
+                            codes::STRING_ARGUMENT,
+                        );
+                        return expr;
+                    }
+
+                    let loader_has_params = match expr.args[0].expr.unwrap_parens() {
+                        Expr::Arrow(ArrowExpr { params, .. }) => !params.is_empty(),
+                        Expr::Fn(FnExpr { function, .. }) => !function.params.is_empty(),
+                        _ => false,
+                    };
+                    if loader_has_params {
+                        self.emit_error(
+                            expr.args[0].expr.span(),
+                            "next/dynamic loaders are called without arguments and can't declare \
+                             parameters",
+                            codes::LOADER_PARAMS,
+                        );
+                        return expr;
+                    }
+
+                    // The calls are still validated above.
+                    if self.is_loadable_disabled {
+                        return expr;
+                    }
+
                     self.is_next_dynamic_first_arg = true;
                     expr.args[0].expr = expr.args[0].expr.clone().fold_with(self);
                     self.is_next_dynamic_first_arg = false;
 
-                    let Some((dynamically_imported_specifier, dynamically_imported_specifier_span)) =
-                        self.dynamically_imported_specifier.take()
-                    else {
+                    let mut specifiers = std::mem::take(&mut self.dynamically_imported_specifiers);
+                    // After the specifiers are captured, so that they are validated too.
+                    if self.config.is_test {
                         return expr;
-                    };
+                    }
+                    if specifiers.is_empty() {
+                        specifiers.extend(
+                            self.resolve_loader_helper(&expr.args[0].expr)
+                                .map(|specifier| self.rewrite_specifier(specifier)),
+                        );
+                    }
+                    if specifiers.is_empty() {
+                        // import load from './load'
+                        // dynamic(load)
+                        if let Expr::Ident(loader) = expr.args[0].expr.unwrap_parens() {
+                            let loader_id = loader.to_id();
+                            if let Some((_, src)) = self
+                                .imported_bindings
+                                .iter()
+                                .find(|(id, _)| *id == loader_id)
+                            {
+                                self.emit_warning(
+                                    loader.span,
+                                    &format!(
+                                        "next/dynamic can't find the module loaded by `{}`, which \
+                                         is imported from '{src}'. No key can be generated for \
+                                         the React Loadable Manifest. Pass `() => import('...')` \
+                                         to `dynamic()` instead.",
+                                        loader.sym
+                                    ),
+                                );
+                            }
+                        }
+                        return expr;
+                    }
+                    if specifiers.len() > 1 {
+                        self.emit_warning(
+                            identifier.span,
+                            "next/dynamic loaders should import a single module. All the modules \
+                             imported by this loader are added to `loadableGenerated`.",
+                        );
+                    }
+                    // dynamic(() => isEnabled && import('../components/hello'))
+                    if let Some(span) = conditional_import(&expr.args[0].expr) {
+                        self.emit_warning(
+                            span,
+                            "next/dynamic loads this module conditionally, which may not match \
+                             the React Loadable Manifest. Import it unconditionally and render it \
+                             conditionally instead.",
+                        );
+                    }
 
-                    let project_dir = match self.pages_or_app_dir.as_deref() {
-                        Some(pages_or_app) => pages_or_app.parent(),
-                        _ => None,
-                    };
+                    if self.config.report_router_boundary_crossings {
+                        for (specifier, span) in &specifiers {
+                            self.report_router_boundary_crossing(specifier, *span);
+                        }
+                    }
+
+                    let mut rel_filename = self.rel_filename();
+                    if self.config.canonical_rel_paths {
+                        rel_filename = canonical_rel_path(&rel_filename);
+                    }
+                    let has_project_dir = self.has_project_dir();
+                    if !has_project_dir
+                        && self.config.warn_missing_project_dir
+                        && (self.state == NextDynamicPatcherState::Webpack
+                            || self.config.hybrid_module_ids)
+                        && !self.reported_missing_project_dir
+                    {
+                        self.reported_missing_project_dir = true;
+                        self.emit_warning(
+                            identifier.span,
+                            "next/dynamic has no pages or app directory to make keys relative to. \
+                             The absolute paths used instead may not match the React Loadable \
+                             Manifest.",
+                        );
+                    }
 
                     // dev client or server:
                     // loadableGenerated: {
@@ -231,49 +1119,165 @@ impl Fold for NextDynamicPatcher {
                     // prod client
                     // loadableGenerated: {
                     //   webpack: () => [require.resolveWeak('../components/hello')],
-                    let generated = Box::new(Expr::Object(ObjectLit {
-                        span: DUMMY_SP,
-                        props: match &mut self.state {
+                    let is_webpack_prod_client = self.state == NextDynamicPatcherState::Webpack
+                        && !self.is_development
+                        && !self.is_server_compiler;
+                    let mut module_ids = Vec::with_capacity(specifiers.len());
+                    let mut unified_module_ids = vec![];
+                    let mut legacy_module_id = None;
+                    let resolve_weak_callee = self.resolve_weak_callee();
+                    let selected_export = if self.config.export_name_keys && specifiers.len() == 1 {
+                        selected_export(&expr.args[0].expr)
+                    } else {
+                        None
+                    };
+                    for (specifier, specifier_span) in &specifiers {
+                        let mut key = None;
+                        let branch;
+                        if self.config.hybrid_module_ids
+                            && self.state != NextDynamicPatcherState::Webpack
+                        {
+                            module_ids.push(quote!(
+                                "$left + $right" as Expr,
+                                left: Expr = format!("{rel_filename} -> ").into(),
+                                right: Expr = specifier.clone().into(),
+                            ));
+                        }
+                        module_ids.push(match &mut self.state {
                             NextDynamicPatcherState::Webpack => {
-                                if self.is_development || self.is_server_compiler {
-                                    module_id_options(quote!(
-                                        "$left + $right" as Expr,
-                                        left: Expr = format!(
-                                            "{} -> ",
-                                            rel_filename(project_dir, &self.filename)
-                                        )
-                                        .into(),
-                                        right: Expr = dynamically_imported_specifier.clone().into(),
-                                    ))
+                                let mut key_filename = if self.config.url_keys {
+                                    url_path(&rel_filename)
+                                } else {
+                                    rel_filename.clone()
+                                };
+                                if self.config.lowercase_key_paths {
+                                    key_filename = key_filename.to_lowercase();
+                                }
+                                let mut key_specifier = if self.config.normalize_specifiers {
+                                    normalize_specifier(specifier)
+                                } else {
+                                    specifier.clone()
+                                };
+                                if self.config.specifier_query == SpecifierQuery::Strip {
+                                    if let Some(index) = key_specifier.find('?') {
+                                        key_specifier.truncate(index);
+                                    }
+                                }
+                                if let Some(max_length) = self.config.max_specifier_length {
+                                    if key_specifier.chars().count() > max_length {
+                                        self.emit_warning(
+                                            *specifier_span,
+                                            &format!(
+                                                "next/dynamic shortened the key of this \
+                                                 specifier, which is longer than {max_length} \
+                                                 characters."
+                                            ),
+                                        );
+                                        key_specifier =
+                                            shorten_specifier(&key_specifier, max_length);
+                                    }
+                                }
+                                if let Some(export) = &selected_export {
+                                    key_specifier = format!("{key_specifier}#{export}");
+                                }
+                                let key_suffix = self.config.module_hash.as_deref().map(|hash| {
+                                    format!("?v={}", hash.chars().take(8).collect::<String>())
+                                });
+
+                                let key_module_id = if self.is_development
+                                    || self.is_server_compiler
+                                    || self.config.unified_keys
+                                {
+                                    if self.config.hash_module_keys {
+                                        let original_key = format!(
+                                            "{key_filename} -> {key_specifier}{}",
+                                            key_suffix.as_deref().unwrap_or_default()
+                                        );
+                                        let hashed_key = hash_module_key(&original_key);
+                                        if !self.hashed_keys.iter().any(|(k, _)| *k == hashed_key) {
+                                            if let Some(buffer) = &self.config.hashed_keys_buffer {
+                                                buffer.lock().unwrap().push((
+                                                    hashed_key.clone(),
+                                                    original_key.clone(),
+                                                ));
+                                            }
+                                            self.hashed_keys
+                                                .push((hashed_key.clone(), original_key));
+                                        }
+                                        key = Some(hashed_key.clone());
+
+                                        Some(hashed_key.into())
+                                    } else {
+                                        let left = format!("{key_filename} -> ");
+                                        let full_key = format!(
+                                            "{left}{key_specifier}{}",
+                                            key_suffix.as_deref().unwrap_or_default()
+                                        );
+                                        key = Some(full_key.clone());
+
+                                        Some(match key_suffix {
+                                            _ if self.config.static_keys => full_key.into(),
+                                            Some(suffix) => quote!(
+                                                "$left + $right + $suffix" as Expr,
+                                                left: Expr = left.into(),
+                                                right: Expr = key_specifier.into(),
+                                                suffix: Expr = suffix.into(),
+                                            ),
+                                            None => quote!(
+                                                "$left + $right" as Expr,
+                                                left: Expr = left.into(),
+                                                right: Expr = key_specifier.into(),
+                                            ),
+                                        })
+                                    }
                                 } else {
-                                    webpack_options(quote!(
-                                        "require.resolveWeak($id)" as Expr,
-                                        id: Expr = dynamically_imported_specifier.clone().into()
-                                    ))
+                                    None
+                                };
+
+                                match key_module_id {
+                                    Some(module_id) if !is_webpack_prod_client => {
+                                        branch = DynamicEntryBranch::WebpackKey;
+                                        module_id
+                                    }
+                                    key_module_id => {
+                                        branch = DynamicEntryBranch::WebpackResolveWeak;
+                                        // With unified keys, the key is also added to `modules`.
+                                        unified_module_ids.extend(key_module_id);
+
+                                        quote!(
+                                            "$callee($id)" as Expr,
+                                            callee = resolve_weak_callee.clone(),
+                                            id: Expr = specifier.clone().into()
+                                        )
+                                    }
                                 }
                             }
                             NextDynamicPatcherState::Turbopack { imports, .. } => {
-                                let id_ident =
-                                    private_ident!(dynamically_imported_specifier_span, "id");
+                                let id_ident = private_ident!(
+                                    *specifier_span,
+                                    turbopack_id_ident_name(
+                                        self.config.private_ident_prefix.as_deref(),
+                                        has_project_dir.then_some(rel_filename.as_str())
+                                    )
+                                );
 
                                 match (self.is_development, self.is_server_compiler) {
                                     (true, true) => {
-                                        let chunks_ident = private_ident!(
-                                            dynamically_imported_specifier_span,
-                                            "chunks"
-                                        );
+                                        let chunks_ident =
+                                            private_ident!(*specifier_span, "chunks");
 
+                                        branch = DynamicEntryBranch::TurbopackDevelopmentTransition;
                                         imports.push(TurbopackImport::DevelopmentTransition {
                                             id_ident: id_ident.clone(),
                                             chunks_ident: chunks_ident.clone(),
-                                            specifier: dynamically_imported_specifier.clone(),
+                                            specifier: specifier.clone(),
                                         });
 
                                         // On the server, the key needs to be serialized because it
                                         // will be used to index the React Loadable Manifest, which
                                         // is a normal JS object. In Turbo mode, this is a proxy,
                                         // but the key will still be coerced to a string.
-                                        module_id_options(quote!(
+                                        quote!(
                                             r#"
                                             JSON.stringify({
                                                 id: $id,
@@ -282,68 +1286,139 @@ impl Fold for NextDynamicPatcher {
                                             "# as Expr,
                                             id = id_ident,
                                             chunks = chunks_ident,
-                                        ))
+                                        )
                                     }
                                     (true, false) => {
+                                        branch = DynamicEntryBranch::TurbopackDevelopmentId;
                                         imports.push(TurbopackImport::DevelopmentId {
                                             id_ident: id_ident.clone(),
-                                            specifier: dynamically_imported_specifier.clone(),
+                                            specifier: specifier.clone(),
                                         });
 
                                         // On the client, we only need the target module ID, which
                                         // will be reported under the `dynamicIds` property of Next
                                         // data.
-                                        module_id_options(Expr::Ident(id_ident))
+                                        Expr::Ident(id_ident)
                                     }
                                     (false, true) => {
-                                        let id_ident = private_ident!(
-                                            dynamically_imported_specifier_span,
-                                            "id"
-                                        );
-
+                                        branch = DynamicEntryBranch::TurbopackBuildTransition;
                                         imports.push(TurbopackImport::BuildTransition {
                                             id_ident: id_ident.clone(),
-                                            specifier: dynamically_imported_specifier.clone(),
+                                            specifier: specifier.clone(),
                                         });
 
-                                        module_id_options(Expr::Ident(id_ident))
+                                        Expr::Ident(id_ident)
                                     }
                                     (false, false) => {
-                                        let id_ident = private_ident!(
-                                            dynamically_imported_specifier_span,
-                                            "id"
-                                        );
-
+                                        branch = DynamicEntryBranch::TurbopackBuildId;
                                         imports.push(TurbopackImport::BuildId {
                                             id_ident: id_ident.clone(),
-                                            specifier: dynamically_imported_specifier.clone(),
+                                            specifier: specifier.clone(),
                                         });
 
-                                        module_id_options(Expr::Ident(id_ident))
+                                        Expr::Ident(id_ident)
                                     }
                                 }
                             }
-                        },
-                    }));
+                        });
+                        // The id of the current mode, not the Webpack key preceding it with
+                        // `hybrid_module_ids`.
+                        if self.config.legacy_module_key && legacy_module_id.is_none() {
+                            legacy_module_id = module_ids.last().cloned();
+                        }
 
-                    let mut props =
-                        vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-                            key: PropName::Ident(IdentName::new(
-                                "loadableGenerated".into(),
-                                DUMMY_SP,
-                            )),
-                            value: generated,
-                        })))];
+                        let entry = DynamicEntry {
+                            key,
+                            specifier: specifier.clone(),
+                            layer: self.layer(),
+                            mode: self.mode(),
+                            branch,
+                        };
+                        if let Some(sink) = &self.config.entry_report_sink {
+                            sink(&entry_report(&self.filename, &entry));
+                        }
+                        self.entries.push(entry);
+                    }
+
+                    if self.config.dry_run {
+                        return expr;
+                    }
+
+                    let mut generated_props = if is_webpack_prod_client {
+                        let mut props = webpack_options(module_ids);
+                        if !unified_module_ids.is_empty() {
+                            let legacy_module_id = if self.config.legacy_module_key {
+                                unified_module_ids.first().cloned()
+                            } else {
+                                None
+                            };
+                            props.extend(module_id_options(
+                                unified_module_ids,
+                                self.config.lazy_module_ids,
+                                legacy_module_id,
+                            ));
+                        }
+                        props
+                    } else {
+                        module_id_options(module_ids, self.config.lazy_module_ids, legacy_module_id)
+                    };
+                    let mut options = vec![];
 
+                    let mut has_ssr_option = false;
                     let mut has_ssr_false = false;
+                    let mut has_prefetch_true = false;
+                    let mut chunk_group = None;
 
-                    if expr.args.len() == 2 {
+                    if expr.args.len() >= 2 {
                         if let Expr::Object(ObjectLit {
                             props: options_props,
                             ..
                         }) = &*expr.args[1].expr
                         {
                             for prop in options_props.iter() {
+                                if let PropOrSpread::Prop(prop) = prop {
+                                    if let Prop::Getter(GetterProp { key, .. })
+                                    | Prop::Setter(SetterProp { key, .. })
+                                    | Prop::Method(MethodProp { key, .. }) = &**prop
+                                    {
+                                        if let PropName::Ident(IdentName { sym, span }) = key {
+                                            if sym == "ssr" {
+                                                // Like any `ssr` value that can't be read at
+                                                // build time, it overrides an earlier
+                                                // `ssr: false`.
+                                                has_ssr_option = true;
+                                                has_ssr_false = false;
+                                            }
+                                            if sym == "ssr" || sym == "prefetch" {
+                                                self.emit_warning(
+                                                    *span,
+                                                    &format!(
+                                                        "next/dynamic can't read the `{sym}` \
+                                                         option of a getter, setter or method. \
+                                                         Use `{sym}: true` or `{sym}: false` \
+                                                         instead."
+                                                    ),
+                                                );
+                                            }
+                                        }
+                                    }
+                                    // const ssr = false
+                                    // dynamic(() => import('../components/hello'), { ssr })
+                                    if let Prop::Shorthand(ident) = &**prop {
+                                        if &*ident.sym == "ssr" {
+                                            has_ssr_option = true;
+                                            let ssr = self
+                                                .module_booleans
+                                                .iter()
+                                                .find(|(id, _)| *id == ident.to_id())
+                                                .map(|(_, value)| *value);
+                                            has_ssr_false = ssr == Some(false);
+                                            if self.config.report_ssr_option_usage {
+                                                self.report_ssr_option_usage(ident.span, ssr);
+                                            }
+                                        }
+                                    }
+                                }
                                 if let Some(KeyValueProp { key, value }) = match prop {
                                     PropOrSpread::Prop(prop) => match &**prop {
                                         Prop::KeyValue(key_value_prop) => Some(key_value_prop),
@@ -351,70 +1426,270 @@ impl Fold for NextDynamicPatcher {
                                     },
                                     _ => None,
                                 } {
-                                    if let Some(IdentName { sym, span: _ }) = match key {
+                                    if let Some(IdentName { sym, span }) = match key {
                                         PropName::Ident(ident) => Some(ident),
                                         _ => None,
                                     } {
                                         if sym == "ssr" {
-                                            if let Some(Lit::Bool(Bool {
-                                                value: false,
-                                                span: _,
-                                            })) = value.as_lit()
+                                            has_ssr_option = true;
+                                            let ssr = match value.unwrap_parens().as_lit() {
+                                                Some(Lit::Bool(Bool { value, .. })) => Some(*value),
+                                                _ => None,
+                                            };
+                                            // The last `ssr` option wins, like in
+                                            // JavaScript objects.
+                                            has_ssr_false = ssr == Some(false);
+                                            if self.config.report_ssr_option_usage {
+                                                self.report_ssr_option_usage(*span, ssr);
+                                            }
+                                        } else if sym == "prefetch" {
+                                            if let Some(Lit::Bool(Bool { value: true, .. })) =
+                                                value.unwrap_parens().as_lit()
+                                            {
+                                                has_prefetch_true = true
+                                            }
+                                        } else if sym == "loading" {
+                                            let is_async = match value.unwrap_parens() {
+                                                Expr::Arrow(arrow) => arrow.is_async,
+                                                Expr::Fn(FnExpr { function, .. }) => {
+                                                    function.is_async
+                                                }
+                                                _ => false,
+                                            };
+                                            if is_async {
+                                                self.emit_warning(
+                                                    *span,
+                                                    "next/dynamic renders the `loading` option as \
+                                                     a React component, which can't be an async \
+                                                     function.",
+                                                );
+                                            }
+                                        } else if sym == "chunkGroup" {
+                                            if let Some(Lit::Str(Str { value, .. })) =
+                                                value.unwrap_parens().as_lit()
                                             {
-                                                has_ssr_false = true
+                                                chunk_group = Some(value.to_string());
                                             }
                                         }
                                     }
                                 }
                             }
-                            props.extend(options_props.iter().cloned());
+                            if !has_ssr_option {
+                                let spread = options_props.iter().find_map(|prop| match prop {
+                                    PropOrSpread::Spread(spread) => Some(spread.dot3_token),
+                                    _ => None,
+                                });
+                                if let Some(span) = spread {
+                                    self.emit_note(
+                                        span,
+                                        "next/dynamic can't resolve the `ssr` option through a \
+                                         spread. Pass `ssr` explicitly for it to be taken into \
+                                         account at build time.",
+                                    );
+                                }
+                            }
+                            options.extend(options_props.iter().cloned());
                         }
                     }
 
-                    if has_ssr_false
-                        && self.is_server_compiler
-                        && !self.is_react_server_layer
-                        // When it's not prefer to picking up ESM, as it's in the pages router, we don't need to do it as it doesn't need to enter the non-ssr module.
-                        // Also transforming it to `require.resolveWeak` and with ESM import, like require.resolveWeak(esm asset) is not available as it's commonjs importing ESM.
-                        && self.prefer_esm
+                    if !has_ssr_option && self.config.ssr_default == SsrDefault::Disabled {
+                        has_ssr_false = true;
+                    }
+                    // const Hello = // next-dynamic: client-only
+                    //   dynamic(() => import('../components/hello'))
+                    if self.has_client_only_comment(expr.span) {
+                        has_ssr_false = true;
+                    }
 
-                        // Only use `require.resolveWebpack` to decouple modules for webpack,
-                        // turbopack doesn't need this
-                        && self.state == NextDynamicPatcherState::Webpack
+                    if has_ssr_false && self.is_server_compiler && self.config.strip_server_loading
                     {
-                        // if it's server components SSR layer
-                        // Transform 1st argument `expr.args[0]` aka the module loader from:
-                        // dynamic(() => import('./client-mod'), { ssr: false }))`
-                        // into:
-                        // dynamic(async () => {
-                        //   require.resolveWeak('./client-mod')
-                        // }, { ssr: false }))`
+                        // The component is never rendered on the server.
+                        options.retain(|prop| !is_prop_named(prop, "loading"));
+                    }
 
-                        let require_resolve_weak_expr = Expr::Call(CallExpr {
+                    if self.config.loadable_generated_ssr {
+                        generated_props.push(key_value_prop(
+                            "ssr",
+                            Box::new(Expr::Lit(Lit::Bool(Bool {
+                                span: DUMMY_SP,
+                                value: !has_ssr_false,
+                            }))),
+                        ));
+                    }
+                    let namespace = self.config.loadable_generated_namespace.as_deref();
+                    let props = if let Some(user_generated) =
+                        find_loadable_generated_mut(&mut options, namespace)
+                    {
+                        // Keep the fields provided by the user.
+                        for prop in generated_props {
+                            let PropOrSpread::Prop(generated_prop) = &prop else {
+                                continue;
+                            };
+                            let Prop::KeyValue(KeyValueProp {
+                                key: PropName::Ident(IdentName { sym, .. }),
+                                ..
+                            }) = &**generated_prop
+                            else {
+                                continue;
+                            };
+                            if !user_generated
+                                .props
+                                .iter()
+                                .any(|user_prop| is_prop_named(user_prop, sym))
+                            {
+                                user_generated.props.push(prop);
+                            }
+                        }
+                        options
+                    } else {
+                        let generated = Box::new(Expr::Object(ObjectLit {
                             span: DUMMY_SP,
-                            callee: quote_ident!("require.resolveWeak").as_callee(),
-                            args: vec![ExprOrSpread {
-                                spread: None,
-                                expr: Box::new(Expr::Lit(Lit::Str(Str {
+                            props: generated_props,
+                        }));
+                        let mut generated_prop = key_value_prop("loadableGenerated", generated);
+                        if let Some(namespace) = namespace {
+                            generated_prop = key_value_prop(
+                                namespace,
+                                Box::new(Expr::Object(ObjectLit {
                                     span: DUMMY_SP,
-                                    value: dynamically_imported_specifier.clone().into(),
-                                    raw: None,
-                                }))),
-                            }],
-                            ..Default::default()
-                        });
+                                    props: vec![generated_prop],
+                                })),
+                            );
+                        }
+                        let mut props = vec![generated_prop];
+                        props.extend(options);
+                        props
+                    };
+
+                    // import { __turbopack_module_id__ as id } from '../components/hello' with {
+                    //   'turbopack-chunk-group': 'widgets' }
+                    if let (
+                        Some(chunk_group),
+                        NextDynamicPatcherState::Turbopack {
+                            import_attribute_keys,
+                            ..
+                        },
+                    ) = (chunk_group, &self.state)
+                    {
+                        for (specifier, _) in &specifiers {
+                            let attribute = (
+                                import_attribute_keys.chunk_group.clone(),
+                                chunk_group.clone(),
+                            );
+                            match self
+                                .import_attributes
+                                .iter_mut()
+                                .find(|(s, _)| s == specifier)
+                            {
+                                Some((_, attributes)) => attributes.push(attribute),
+                                None => self
+                                    .import_attributes
+                                    .push((specifier.clone(), vec![attribute])),
+                            }
+                        }
+                    }
+
+                    let resolve_weak_context = ResolveWeakContext {
+                        is_server_compiler: self.is_server_compiler,
+                        is_react_server_layer: self.is_react_server_layer,
+                        prefer_esm: self.prefer_esm,
+                        has_ssr_false,
+                        mode: self.mode(),
+                    };
+                    let use_resolve_weak = match &self.config.resolve_weak_policy {
+                        Some(policy) => policy(&resolve_weak_context),
+                        None => resolve_weak_context.default_policy(),
+                    };
+                    let asset_specifier = specifiers
+                        .iter()
+                        .find(|(specifier, _)| is_asset_specifier(specifier));
+                    if let (true, Some((specifier, _))) = (use_resolve_weak, asset_specifier) {
+                        self.emit_note(
+                            identifier.span,
+                            &format!(
+                                "`{specifier}` is an asset, so the loader of this `ssr: false` \
+                                 call isn't replaced with `require.resolveWeak`."
+                            ),
+                        );
+                    }
+
+                    let is_loader_replaced = use_resolve_weak && asset_specifier.is_none();
+
+                    // The `import()` calls are gone once the loader is replaced, and
+                    // shouldn't be added back by the preload exports either.
+                    if self.config.preload_exports
+                        && self.state == NextDynamicPatcherState::Webpack
+                        && !is_loader_replaced
+                    {
+                        for (specifier, _) in &specifiers {
+                            if !self.preload_specifiers.contains(specifier) {
+                                self.preload_specifiers.push(specifier.clone());
+                            }
+                        }
+                    }
+
+                    if has_prefetch_true
+                        && self.config.prefetch_option
+                        && self.state == NextDynamicPatcherState::Webpack
+                        && !is_loader_replaced
+                    {
+                        // import(/* webpackPrefetch: true */ '../components/hello')
+                        let mut import_args = ImportArgSpans::default();
+                        expr.args[0].expr.visit_with(&mut import_args);
+                        for span in import_args.spans {
+                            self.comments.add_leading(
+                                span.lo,
+                                Comment {
+                                    kind: CommentKind::Block,
+                                    span: DUMMY_SP,
+                                    text: " webpackPrefetch: true ".into(),
+                                },
+                            );
+                        }
+                    }
+
+                    if is_loader_replaced {
+                        // if it's server components SSR layer
+                        // Transform 1st argument `expr.args[0]` aka the module loader from:
+                        // dynamic(() => import('./client-mod'), { ssr: false }))`
+                        // into:
+                        // dynamic(async () => {
+                        //   require.resolveWeak('./client-mod')
+                        // }, { ssr: false }))`
+
+                        let stmts = specifiers
+                            .iter()
+                            .map(|(specifier, _)| {
+                                let require_resolve_weak_expr = Expr::Call(CallExpr {
+                                    span: DUMMY_SP,
+                                    callee: resolve_weak_callee.clone().as_callee(),
+                                    args: vec![ExprOrSpread {
+                                        spread: None,
+                                        expr: Box::new(Expr::Lit(Lit::Str(Str {
+                                            span: DUMMY_SP,
+                                            value: specifier.clone().into(),
+                                            raw: None,
+                                        }))),
+                                    }],
+                                    ..Default::default()
+                                });
+
+                                Stmt::Expr(ExprStmt {
+                                    span: DUMMY_SP,
+                                    expr: Box::new(exec_expr_when_resolve_weak_available(
+                                        &resolve_weak_callee,
+                                        &require_resolve_weak_expr,
+                                    )),
+                                })
+                            })
+                            .collect();
 
                         let side_effect_free_loader_arg = Expr::Arrow(ArrowExpr {
                             span: DUMMY_SP,
                             params: vec![],
                             body: Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
                                 span: DUMMY_SP,
-                                stmts: vec![Stmt::Expr(ExprStmt {
-                                    span: DUMMY_SP,
-                                    expr: Box::new(exec_expr_when_resolve_weak_available(
-                                        &require_resolve_weak_expr,
-                                    )),
-                                })],
+                                stmts,
                                 ..Default::default()
                             })),
                             is_async: true,
@@ -425,15 +1700,36 @@ impl Fold for NextDynamicPatcher {
                         expr.args[0] = side_effect_free_loader_arg.as_arg();
                     }
 
+                    let options = ObjectLit {
+                        span: DUMMY_SP,
+                        props,
+                    };
+                    if let Some(inspector) = &self.config.options_inspector {
+                        inspector(&options);
+                    }
+                    let mut options = Expr::Object(options);
+                    if let Some(guard) = &self.config.loadable_generated_guard {
+                        let original = match expr.args.get(1) {
+                            Some(arg) => arg.expr.clone(),
+                            None => Box::new(quote!("void 0" as Expr)),
+                        };
+                        options = Expr::Cond(CondExpr {
+                            span: DUMMY_SP,
+                            test: Box::new(quote!(
+                                "typeof $guard !== \"undefined\"" as Expr,
+                                guard =
+                                    Ident::new(guard.as_str().into(), DUMMY_SP, Default::default()),
+                            )),
+                            cons: Box::new(options),
+                            alt: original,
+                        });
+                    }
                     let second_arg = ExprOrSpread {
                         spread: None,
-                        expr: Box::new(Expr::Object(ObjectLit {
-                            span: DUMMY_SP,
-                            props,
-                        })),
+                        expr: Box::new(options),
                     };
 
-                    if expr.args.len() == 2 {
+                    if expr.args.len() >= 2 {
                         expr.args[1] = second_arg;
                     } else {
                         expr.args.push(second_arg)
@@ -445,252 +1741,3330 @@ impl Fold for NextDynamicPatcher {
     }
 }
 
-fn module_id_options(module_id: Expr) -> Vec<PropOrSpread> {
-    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-        key: PropName::Ident(IdentName::new("modules".into(), DUMMY_SP)),
-        value: Box::new(Expr::Array(ArrayLit {
-            elems: vec![Some(ExprOrSpread {
-                expr: Box::new(module_id),
-                spread: None,
-            })],
-            span: DUMMY_SP,
-        })),
-    })))]
+struct LoadableGeneratedStripper {
+    mode: NextDynamicMode,
+    config: Config,
+    dynamic_bindings: Vec<Id>,
+    /// The specifiers imported by the loaders of the transformed calls.
+    loaded_specifiers: Vec<String>,
 }
 
-fn webpack_options(module_id: Expr) -> Vec<PropOrSpread> {
-    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-        key: PropName::Ident(IdentName::new("webpack".into(), DUMMY_SP)),
-        value: Box::new(Expr::Arrow(ArrowExpr {
-            params: vec![],
-            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Array(ArrayLit {
-                elems: vec![Some(ExprOrSpread {
-                    expr: Box::new(module_id),
-                    spread: None,
-                })],
-                span: DUMMY_SP,
-            })))),
-            is_async: false,
-            is_generator: false,
-            span: DUMMY_SP,
-            ..Default::default()
-        })),
-    })))]
-}
+impl LoadableGeneratedStripper {
+    fn is_dynamic_call(&self, expr: &CallExpr) -> bool {
+        let Callee::Expr(callee) = &expr.callee else {
+            return false;
+        };
 
-impl NextDynamicPatcher {
-    fn maybe_add_dynamically_imported_specifier(&mut self, items: &mut Vec<ModuleItem>) {
-        let NextDynamicPatcherState::Turbopack {
+        callee_ident(callee).is_some_and(|ident| self.dynamic_bindings.contains(&ident.to_id()))
+    }
+
+    /// Whether `item` is one of the imports of [TurbopackImport], which import
+    /// the module id under a private `id` binding.
+    fn is_generated_import(&self, item: &ModuleItem) -> bool {
+        let NextDynamicMode::Turbopack {
             dynamic_transition_name,
-            imports,
-        } = &mut self.state
+            import_attribute_keys: keys,
+            module_id_export,
+            ..
+        } = &self.mode
         else {
-            return;
+            return false;
+        };
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+            specifiers,
+            with: Some(with),
+            ..
+        })) = item
+        else {
+            return false;
         };
 
-        let mut new_items = Vec::with_capacity(imports.len());
+        let has_attribute = |key: &str, expected: &str| {
+            matches!(
+                find_prop(with, key),
+                Some(Expr::Lit(Lit::Str(Str { value, .. }))) if &**value == expected
+            )
+        };
+        let is_transition = has_attribute(&keys.transition, dynamic_transition_name);
+        let is_id_only = has_attribute(&keys.chunking_type, "none");
+        // See `turbopack_id_ident_name`, the hygiene pass may have suffixed it
+        // with a number.
+        let prefix = self.config.private_ident_prefix.as_deref().unwrap_or("id_");
+        let is_id = |ident: &Ident| {
+            let name = ident.sym.trim_end_matches(|c: char| c.is_ascii_digit());
+            name == prefix.trim_end_matches('_') || name.starts_with(prefix)
+        };
 
-        for import in std::mem::take(imports) {
-            match import {
-                TurbopackImport::DevelopmentTransition {
-                    id_ident,
-                    chunks_ident,
-                    specifier,
-                } => {
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span: DUMMY_SP,
-                        specifiers: vec![
-                            ImportSpecifier::Default(ImportDefaultSpecifier {
-                                span: DUMMY_SP,
-                                local: id_ident,
-                            }),
-                            ImportSpecifier::Named(ImportNamedSpecifier {
-                                span: DUMMY_SP,
-                                local: chunks_ident,
-                                imported: Some(
-                                    Ident::new("chunks".into(), DUMMY_SP, Default::default())
-                                        .into(),
-                                ),
-                                is_type_only: false,
-                            }),
-                        ],
-                        src: Box::new(specifier.into()),
-                        type_only: false,
-                        // The transition should return both the target module's id
-                        // and the chunks it needs to run.
-                        with: Some(with_transition(dynamic_transition_name)),
-                        phase: Default::default(),
-                    })));
-                }
-                TurbopackImport::DevelopmentId {
-                    id_ident,
-                    specifier,
-                } => {
-                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
-                    // identifier into the imported module's id.
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span: DUMMY_SP,
-                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                            span: DUMMY_SP,
-                            local: id_ident,
-                            imported: Some(
-                                Ident::new(
-                                    "__turbopack_module_id__".into(),
-                                    DUMMY_SP,
-                                    Default::default(),
-                                )
-                                .into(),
-                            ),
-                            is_type_only: false,
-                        })],
-                        src: Box::new(specifier.into()),
-                        type_only: false,
-                        // We don't want this import to cause the imported module to be considered
-                        // for chunking through this import; we only need
-                        // the module id.
-                        with: Some(with_chunking_type("none")),
-                        phase: Default::default(),
-                    })));
-                }
-                TurbopackImport::BuildTransition {
-                    id_ident,
-                    specifier,
-                } => {
-                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
-                    // identifier into the imported module's id.
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span: DUMMY_SP,
-                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                            span: DUMMY_SP,
-                            local: id_ident,
-                            imported: Some(
-                                Ident::new(
-                                    "__turbopack_module_id__".into(),
-                                    DUMMY_SP,
-                                    Default::default(),
-                                )
-                                .into(),
-                            ),
-                            is_type_only: false,
-                        })],
-                        src: Box::new(specifier.into()),
-                        type_only: false,
-                        // The transition should make sure the imported module ends up in the
-                        // dynamic manifest.
-                        with: Some(with_transition(dynamic_transition_name)),
-                        phase: Default::default(),
-                    })));
-                }
-                TurbopackImport::BuildId {
-                    id_ident,
-                    specifier,
-                } => {
-                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
-                    // identifier into the imported module's id.
-                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
-                        span: DUMMY_SP,
-                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
-                            span: DUMMY_SP,
-                            local: id_ident,
-                            imported: Some(
-                                Ident::new(
-                                    "__turbopack_module_id__".into(),
-                                    DUMMY_SP,
-                                    Default::default(),
-                                )
-                                .into(),
-                            ),
-                            is_type_only: false,
-                        })],
-                        src: Box::new(specifier.into()),
-                        type_only: false,
-                        // We don't want this import to cause the imported module to be considered
-                        // for chunking through this import; we only need
-                        // the module id.
-                        with: Some(with_chunking_type("none")),
-                        phase: Default::default(),
-                    })));
-                }
+        let module_id_export = module_id_export
+            .as_deref()
+            .unwrap_or("__turbopack_module_id__");
+        let imported_as = |specifier: &ImportSpecifier, export: &str| match specifier {
+            ImportSpecifier::Named(ImportNamedSpecifier {
+                local,
+                imported: Some(ModuleExportName::Ident(imported)),
+                ..
+            }) if &*imported.sym == export => Some(local.clone()),
+            _ => None,
+        };
+
+        match &**specifiers {
+            // import { __turbopack_module_id__ as id } from '...' with { ... }
+            [id] => {
+                (is_transition || is_id_only)
+                    && imported_as(id, module_id_export).is_some_and(|id| is_id(&id))
+            }
+            // import id, { chunks } from '...' with { transition: '...' }
+            [ImportSpecifier::Default(id), chunks] => {
+                is_transition
+                    && is_id(&id.local)
+                    && imported_as(chunks, "chunks")
+                        .is_some_and(|chunks| chunks.sym.starts_with("chunks"))
             }
+            _ => false,
         }
+    }
 
-        new_items.append(items);
+    /// Whether `item` has the shape of the exports of [Config::preload_exports],
+    /// like `export const __preload_hello = () => import('../components/hello')`
+    /// for a module loaded by a transformed call.
+    fn is_generated_preload_export(&self, item: &ModuleItem) -> bool {
+        if !self.config.preload_exports {
+            return false;
+        }
+        let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+            decl: Decl::Var(var),
+            ..
+        })) = item
+        else {
+            return false;
+        };
+        let [VarDeclarator {
+            name: Pat::Ident(name),
+            init: Some(init),
+            ..
+        }] = &*var.decls
+        else {
+            return false;
+        };
+        let Expr::Arrow(ArrowExpr { params, body, .. }) = &**init else {
+            return false;
+        };
+        let BlockStmtOrExpr::Expr(body) = &**body else {
+            return false;
+        };
+        let Expr::Call(CallExpr {
+            callee: Callee::Import(..),
+            args,
+            ..
+        }) = &**body
+        else {
+            return false;
+        };
 
-        std::mem::swap(&mut new_items, items)
+        var.kind == VarDeclKind::Const
+            && name.sym.starts_with("__preload_")
+            && params.is_empty()
+            && matches!(
+                &**args,
+                [ExprOrSpread { spread: None, expr }]
+                    if matches!(
+                        &**expr,
+                        Expr::Lit(Lit::Str(Str { value, .. }))
+                            if self.loaded_specifiers.iter().any(|s| **s == **value)
+                    )
+            )
     }
-}
 
-fn exec_expr_when_resolve_weak_available(expr: &Expr) -> Expr {
-    let undefined_str_literal = Expr::Lit(Lit::Str(Str {
-        span: DUMMY_SP,
-        value: "undefined".into(),
-        raw: None,
-    }));
+    /// The options passed to `dynamic()` before the transform, which are
+    /// `Some(None)` if there were none, or `None` if `options` weren't
+    /// generated.
+    fn original_options(&self, options: &Expr) -> Option<Option<Box<Expr>>> {
+        let namespace = self.config.loadable_generated_namespace.as_deref();
+        match options {
+            Expr::Object(options) if has_loadable_generated(options, namespace) => {
+                let mut props = options.props.clone();
+                match namespace {
+                    None => props.retain(|prop| !is_prop_named(prop, "loadableGenerated")),
+                    Some(namespace) => {
+                        if let Some(Expr::Object(namespaced)) = find_prop_mut(&mut props, namespace)
+                        {
+                            namespaced
+                                .props
+                                .retain(|prop| !is_prop_named(prop, "loadableGenerated"));
+                            if namespaced.props.is_empty() {
+                                props.retain(|prop| !is_prop_named(prop, namespace));
+                            }
+                        }
+                    }
+                }
 
-    let typeof_expr = Expr::Unary(UnaryExpr {
-        span: DUMMY_SP,
-        op: UnaryOp::TypeOf, // 'typeof' operator
-        arg: Box::new(Expr::Ident(Ident {
-            sym: quote_ident!("require.resolveWeak").sym,
-            ..Default::default()
-        })),
-    });
+                // The options object was added by the transform.
+                Some((!props.is_empty()).then(|| {
+                    Box::new(Expr::Object(ObjectLit {
+                        span: options.span,
+                        props,
+                    }))
+                }))
+            }
+            // typeof guard !== "undefined" ? { loadableGenerated: ... } : original
+            Expr::Cond(CondExpr {
+                test, cons, alt, ..
+            }) => {
+                let guard = self.config.loadable_generated_guard.as_deref()?;
+                if !is_guard_test(test, guard)
+                    || !cons
+                        .as_object()
+                        .is_some_and(|options| has_loadable_generated(options, namespace))
+                {
+                    return None;
+                }
 
-    // typeof require.resolveWeak !== 'undefined' && <expression>
-    Expr::Bin(BinExpr {
-        span: DUMMY_SP,
-        left: Box::new(Expr::Bin(BinExpr {
-            span: DUMMY_SP,
-            op: op!("!=="),
-            left: Box::new(typeof_expr),
-            right: Box::new(undefined_str_literal),
-        })),
-        op: op!("&&"),
-        right: Box::new(expr.clone()),
-    })
+                Some((!is_void_zero(alt)).then(|| alt.clone()))
+            }
+            _ => None,
+        }
+    }
 }
 
-fn rel_filename(base: Option<&Path>, file: &FileName) -> String {
-    let base = match base {
-        Some(v) => v,
-        None => return file.to_string(),
+impl Fold for LoadableGeneratedStripper {
+    fn fold_program(&mut self, program: Program) -> Program {
+        let mut bindings = DynamicBindings {
+            config: &self.config,
+            ids: vec![],
+        };
+        program.visit_with(&mut bindings);
+        self.dynamic_bindings = bindings.ids;
+
+        let mut loaded_specifiers = LoadedSpecifiers {
+            stripper: self,
+            is_loader: false,
+            specifiers: vec![],
+        };
+        program.visit_with(&mut loaded_specifiers);
+        self.loaded_specifiers = loaded_specifiers.specifiers;
+
+        program.fold_children_with(self)
+    }
+
+    fn fold_module_items(&mut self, mut items: Vec<ModuleItem>) -> Vec<ModuleItem> {
+        // The preload exports are appended to the module.
+        let preload_exports = items
+            .iter()
+            .rev()
+            .take_while(|item| self.is_generated_preload_export(item))
+            .count();
+        items.truncate(items.len() - preload_exports);
+
+        items
+            .into_iter()
+            .filter(|item| !self.is_generated_import(item))
+            .collect::<Vec<_>>()
+            .fold_children_with(self)
+    }
+
+    fn fold_call_expr(&mut self, expr: CallExpr) -> CallExpr {
+        let mut expr = expr.fold_children_with(self);
+        // The extra arguments of `Config::skip_argument_count_validation` are kept.
+        if !self.is_dynamic_call(&expr) || expr.args.len() < 2 || expr.args[1].spread.is_some() {
+            return expr;
+        }
+
+        match self.original_options(&expr.args[1].expr) {
+            Some(Some(options)) => expr.args[1].expr = options,
+            // The options were an empty object if there are more arguments.
+            Some(None) if expr.args.len() > 2 => {
+                expr.args[1].expr = Box::new(Expr::Object(ObjectLit {
+                    span: DUMMY_SP,
+                    props: vec![],
+                }));
+            }
+            Some(None) => {
+                expr.args.pop();
+            }
+            None => {}
+        }
+
+        expr
+    }
+}
+
+/// Collects the specifiers imported by the loaders of the calls transformed by
+/// [next_dynamic], see [LoadableGeneratedStripper::is_generated_preload_export].
+struct LoadedSpecifiers<'a> {
+    stripper: &'a LoadableGeneratedStripper,
+    is_loader: bool,
+    specifiers: Vec<String>,
+}
+
+impl Visit for LoadedSpecifiers<'_> {
+    fn visit_call_expr(&mut self, expr: &CallExpr) {
+        if let (true, Callee::Import(..), Some(arg)) =
+            (self.is_loader, &expr.callee, expr.args.first())
+        {
+            if let Some((specifier, _)) = static_specifier(&arg.expr) {
+                self.specifiers.push(specifier);
+            }
+        }
+
+        if self.stripper.is_dynamic_call(expr)
+            && expr.args.len() >= 2
+            && self.stripper.original_options(&expr.args[1].expr).is_some()
+        {
+            let is_loader = std::mem::replace(&mut self.is_loader, true);
+            expr.args[0].visit_with(self);
+            self.is_loader = is_loader;
+            expr.args[1..].visit_with(self);
+            return;
+        }
+
+        expr.visit_children_with(self);
+    }
+}
+
+/// Collects the bindings of `next/dynamic` in a module, like
+/// [NextDynamicPatcher] does while transforming it.
+struct DynamicBindings<'a> {
+    config: &'a Config,
+    ids: Vec<Id>,
+}
+
+impl Visit for DynamicBindings<'_> {
+    fn visit_import_decl(&mut self, decl: &ImportDecl) {
+        if self.config.is_dynamic_source(&decl.src.value) {
+            for specifier in &decl.specifiers {
+                if let ImportSpecifier::Default(default_specifier) = specifier {
+                    self.ids.push(default_specifier.local.to_id());
+                }
+            }
+        }
+    }
+
+    fn visit_var_decl(&mut self, decl: &VarDecl) {
+        for declarator in &decl.decls {
+            let (Pat::Ident(name), Some(init)) = (&declarator.name, &declarator.init) else {
+                continue;
+            };
+            // const dynamic = require('next/dynamic')
+            let is_require = is_next_dynamic_require(init);
+            // const d = dynamic
+            let is_alias = self.config.follow_const_aliases
+                && decl.kind == VarDeclKind::Const
+                && matches!(&**init, Expr::Ident(target) if self.ids.contains(&target.to_id()));
+            if is_require || is_alias {
+                self.ids.push(name.id.to_id());
+            }
+        }
+
+        decl.visit_children_with(self);
+    }
+}
+
+/// Whether `test` is `typeof guard !== "undefined"`, see
+/// [Config::loadable_generated_guard].
+fn is_guard_test(test: &Expr, guard: &str) -> bool {
+    let Expr::Bin(BinExpr {
+        op: op!("!=="),
+        left,
+        right,
+        ..
+    }) = test
+    else {
+        return false;
     };
 
-    let file = match file {
-        FileName::Real(v) => v,
-        _ => {
-            return file.to_string();
+    matches!(
+        &**left,
+        Expr::Unary(UnaryExpr {
+            op: op!("typeof"),
+            arg,
+            ..
+        }) if matches!(&**arg, Expr::Ident(ident) if &*ident.sym == guard)
+    ) && matches!(
+        &**right,
+        Expr::Lit(Lit::Str(Str { value, .. })) if &**value == "undefined"
+    )
+}
+
+fn is_void_zero(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Unary(UnaryExpr {
+            op: op!("void"),
+            arg,
+            ..
+        }) if matches!(&**arg, Expr::Lit(Lit::Num(_)))
+    )
+}
+
+/// Collects the identifiers that are assigned to anywhere in a module.
+#[derive(Default)]
+struct ReassignedIdents {
+    ids: Vec<Id>,
+}
+
+impl Visit for ReassignedIdents {
+    fn visit_assign_expr(&mut self, expr: &AssignExpr) {
+        match &expr.left {
+            AssignTarget::Simple(SimpleAssignTarget::Ident(ident)) => {
+                self.ids.push(ident.id.to_id());
+            }
+            // [dynamic] = x, ({ dynamic } = x)
+            AssignTarget::Pat(pat) => self.ids.extend(find_pat_ids::<_, Id>(pat)),
+            _ => {}
+        }
+
+        expr.visit_children_with(self);
+    }
+
+    // dynamic++
+    fn visit_update_expr(&mut self, expr: &UpdateExpr) {
+        if let Expr::Ident(ident) = expr.arg.unwrap_parens() {
+            self.ids.push(ident.to_id());
+        }
+
+        expr.visit_children_with(self);
+    }
+}
+
+/// Collects the spans of the specifiers passed to `import()`, including
+/// template literals.
+#[derive(Default)]
+struct ImportArgSpans {
+    spans: Vec<Span>,
+}
+
+impl Visit for ImportArgSpans {
+    fn visit_call_expr(&mut self, expr: &CallExpr) {
+        if let (Callee::Import(..), Some(arg)) = (&expr.callee, expr.args.first()) {
+            self.spans.push(arg.expr.span());
         }
+
+        expr.visit_children_with(self);
+    }
+}
+
+/// Collects the identifiers referenced in a module, outside of its imports.
+#[derive(Default)]
+struct ReferencedIdents {
+    ids: Vec<Id>,
+}
+
+impl Visit for ReferencedIdents {
+    fn visit_import_decl(&mut self, _: &ImportDecl) {}
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.ids.push(ident.to_id());
+    }
+}
+
+/// `../components/hello.js` is exported as `__preload_hello`.
+fn preload_export_name(specifier: &str) -> String {
+    let file_name = specifier.rsplit('/').next().unwrap_or(specifier);
+    let file_stem = match file_name.split_once('.') {
+        Some((stem, _)) if !stem.is_empty() => stem,
+        _ => file_name,
     };
 
-    let rel_path = diff_paths(file, base);
+    let name: String = file_stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
 
-    let rel_path = match rel_path {
-        Some(v) => v,
-        None => return file.display().to_string(),
+    format!("__preload_{name}")
+}
+
+fn has_directive(items: &[ModuleItem], directive: &str) -> bool {
+    items
+        .iter()
+        .map_while(|item| match item {
+            ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+                Expr::Lit(Lit::Str(Str { value, .. })) => Some(value),
+                _ => None,
+            },
+            _ => None,
+        })
+        .any(|value| &**value == directive)
+}
+
+/// Matches `require('next/dynamic')` and `require('next/dynamic').default`.
+fn is_next_dynamic_require(expr: &Expr) -> bool {
+    let expr = match expr.unwrap_parens() {
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) if &*prop.sym == "default" => obj.unwrap_parens(),
+        expr => expr,
     };
 
-    rel_path.display().to_string()
+    match expr {
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) if args.len() == 1 => {
+            matches!(&**callee, Expr::Ident(Ident { sym, .. }) if sym == "require")
+                && matches!(
+                    &*args[0].expr,
+                    Expr::Lit(Lit::Str(Str { value, .. })) if value == "next/dynamic"
+                )
+        }
+        _ => false,
+    }
 }
 
-fn with_chunking_type(chunking_type: &str) -> Box<ObjectLit> {
-    with_clause(&[("chunking-type", chunking_type)])
+/// Sees through parentheses and the TypeScript wrappers that don't change the
+/// runtime value, like `import('./x') as Promise<any>`.
+fn unwrap_ts_and_parens(mut expr: &Expr) -> &Expr {
+    loop {
+        expr = match expr {
+            Expr::Paren(ParenExpr { expr, .. })
+            | Expr::TsAs(TsAsExpr { expr, .. })
+            | Expr::TsConstAssertion(TsConstAssertion { expr, .. })
+            | Expr::TsNonNull(TsNonNullExpr { expr, .. }) => expr,
+            _ => return expr,
+        }
+    }
 }
 
-fn with_transition(transition_name: &str) -> Box<ObjectLit> {
-    with_clause(&[("transition", transition_name)])
+/// The export selected by loaders of the form `() =>
+/// import('...').then((mod) => mod.Name)` or `async () => (await
+/// import('...')).Name`.
+fn selected_export(loader: &Expr) -> Option<Atom> {
+    let Expr::Arrow(ArrowExpr { body, .. }) = unwrap_ts_and_parens(loader) else {
+        return None;
+    };
+    let BlockStmtOrExpr::Expr(body) = &**body else {
+        return None;
+    };
+
+    match unwrap_ts_and_parens(body) {
+        // (await import('...')).Name
+        Expr::Member(MemberExpr {
+            obj,
+            prop: MemberProp::Ident(prop),
+            ..
+        }) => {
+            let Expr::Await(AwaitExpr { arg, .. }) = unwrap_ts_and_parens(obj) else {
+                return None;
+            };
+            is_import_call(arg).then(|| prop.sym.clone())
+        }
+        // import('...').then((mod) => mod.Name)
+        Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            args,
+            ..
+        }) if args.len() == 1 => {
+            let Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(then),
+                ..
+            }) = &**callee
+            else {
+                return None;
+            };
+            if &*then.sym != "then" || !is_import_call(obj) {
+                return None;
+            }
+            let Expr::Arrow(ArrowExpr { body, .. }) = unwrap_ts_and_parens(&args[0].expr) else {
+                return None;
+            };
+            match &**body {
+                BlockStmtOrExpr::Expr(body) => match unwrap_ts_and_parens(body) {
+                    Expr::Member(MemberExpr {
+                        prop: MemberProp::Ident(prop),
+                        ..
+                    }) => Some(prop.sym.clone()),
+                    _ => None,
+                },
+                BlockStmtOrExpr::BlockStmt(_) => None,
+            }
+        }
+        _ => None,
+    }
 }
 
-fn with_clause<'a>(entries: impl IntoIterator<Item = &'a (&'a str, &'a str)>) -> Box<ObjectLit> {
-    Box::new(ObjectLit {
-        span: DUMMY_SP,
-        props: entries.into_iter().map(|(k, v)| with_prop(k, v)).collect(),
+/// The span of the `&&` or `||` expression returned by loaders of the form `()
+/// => isEnabled && import('...')`, when a single one of its operands imports a
+/// module.
+fn conditional_import(loader: &Expr) -> Option<Span> {
+    fn count_imports(expr: &Expr) -> usize {
+        match unwrap_ts_and_parens(expr) {
+            Expr::Bin(BinExpr {
+                op: op!("&&") | op!("||"),
+                left,
+                right,
+                ..
+            }) => count_imports(left) + count_imports(right),
+            expr => is_import_call(expr) as usize,
+        }
+    }
+
+    let Expr::Arrow(ArrowExpr { body, .. }) = unwrap_ts_and_parens(loader) else {
+        return None;
+    };
+    let BlockStmtOrExpr::Expr(body) = &**body else {
+        return None;
+    };
+
+    let body = unwrap_ts_and_parens(body);
+    match body {
+        Expr::Bin(BinExpr {
+            op: op!("&&") | op!("||"),
+            span,
+            ..
+        }) if count_imports(body) == 1 => Some(*span),
+        _ => None,
+    }
+}
+
+/// Assets can't always be resolved with `require.resolveWeak`.
+const ASSET_EXTENSIONS: &[&str] = &[
+    "json", "css", "scss", "sass", "less", "svg", "png", "jpg", "jpeg", "gif", "webp", "avif",
+    "ico",
+];
+
+fn is_asset_specifier(specifier: &str) -> bool {
+    let path = specifier.split(['?', '#']).next().unwrap_or(specifier);
+
+    Path::new(path)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| ASSET_EXTENSIONS.contains(&extension))
+}
+
+fn is_import_call(expr: &Expr) -> bool {
+    matches!(
+        unwrap_ts_and_parens(expr),
+        Expr::Call(CallExpr {
+            callee: Callee::Import(..),
+            ..
+        })
+    )
+}
+
+/// The identifier called by `dynamic(...)`, or by the indirect call `(0,
+/// dynamic)(...)` emitted by some bundlers.
+fn callee_ident(callee: &Expr) -> Option<&Ident> {
+    match callee {
+        Expr::Ident(ident) => Some(ident),
+        Expr::Paren(ParenExpr { expr, .. }) => match &**expr {
+            Expr::Seq(SeqExpr { exprs, .. }) => {
+                let (last, rest) = exprs.split_last()?;
+                if rest.iter().all(|expr| expr.is_lit()) {
+                    last.as_ident()
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The string attributes of the `with` option of an `import()` call, like `{
+/// type: 'worker' }` in `import('./worker', { with: { type: 'worker' } })`.
+fn import_attributes(import: &CallExpr) -> Vec<(String, String)> {
+    let Some(Expr::Object(options)) = import.args.get(1).map(|arg| arg.expr.unwrap_parens()) else {
+        return vec![];
+    };
+    let Some(Expr::Object(with)) = find_prop(options, "with").map(Expr::unwrap_parens) else {
+        return vec![];
+    };
+
+    with.props
+        .iter()
+        .filter_map(|prop| {
+            let PropOrSpread::Prop(prop) = prop else {
+                return None;
+            };
+            let Prop::KeyValue(KeyValueProp { key, value }) = &**prop else {
+                return None;
+            };
+            let key = match key {
+                PropName::Ident(IdentName { sym, .. }) => sym.to_string(),
+                PropName::Str(Str { value, .. }) => value.to_string(),
+                _ => return None,
+            };
+            let Expr::Lit(Lit::Str(value)) = value.unwrap_parens() else {
+                return None;
+            };
+
+            Some((key, value.value.to_string()))
+        })
+        .collect()
+}
+
+/// Whether `arg` is of the form `new URL('./x', import.meta.url)`.
+fn is_import_meta_url(arg: &Expr) -> bool {
+    let Expr::New(NewExpr {
+        callee,
+        args: Some(args),
+        ..
+    }) = unwrap_ts_and_parens(arg)
+    else {
+        return false;
+    };
+
+    matches!(&**callee, Expr::Ident(Ident { sym, .. }) if &**sym == "URL")
+        && args.len() == 2
+        && matches!(
+            &*args[1].expr,
+            Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(IdentName { sym, .. }),
+                ..
+            }) if &**sym == "url"
+                && matches!(
+                    &**obj,
+                    Expr::MetaProp(MetaPropExpr {
+                        kind: MetaPropKind::ImportMeta,
+                        ..
+                    })
+                )
+        )
+}
+
+fn static_specifier(arg: &Expr) -> Option<(String, Span)> {
+    match unwrap_ts_and_parens(arg) {
+        Expr::Lit(Lit::Str(Str { value, span, .. })) => Some((value.to_string(), *span)),
+        // The padding of template literals is most likely unintended.
+        Expr::Tpl(Tpl { exprs, quasis, .. }) if exprs.is_empty() => {
+            Some((quasis[0].raw.trim().to_string(), quasis[0].span))
+        }
+        _ => None,
+    }
+}
+
+/// Finds the `const helper = () => import('./x')` declarations of a module.
+/// Arrow functions with a different body are also returned, without a
+/// specifier, so that calls to them can be reported.
+fn collect_loader_helpers(items: &[ModuleItem]) -> Vec<(Id, Option<(String, Span)>)> {
+    let mut helpers = vec![];
+
+    for item in items {
+        let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) = item else {
+            continue;
+        };
+        if var.kind != VarDeclKind::Const {
+            continue;
+        }
+
+        for decl in &var.decls {
+            let (Pat::Ident(name), Some(init)) = (&decl.name, &decl.init) else {
+                continue;
+            };
+            let Expr::Arrow(arrow) = init.unwrap_parens() else {
+                continue;
+            };
+
+            let specifier = match &*arrow.body {
+                BlockStmtOrExpr::Expr(body) if arrow.params.is_empty() => {
+                    match unwrap_ts_and_parens(body) {
+                        Expr::Call(CallExpr {
+                            callee: Callee::Import(..),
+                            args,
+                            ..
+                        }) if !args.is_empty() => static_specifier(&args[0].expr),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            helpers.push((name.id.to_id(), specifier));
+        }
+    }
+
+    helpers
+}
+
+fn collect_module_booleans(items: &[ModuleItem]) -> Vec<(Id, bool)> {
+    let mut booleans = vec![];
+
+    for item in items {
+        let var = match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Var(var)))
+            | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Var(var),
+                ..
+            })) => var,
+            _ => continue,
+        };
+        if var.kind != VarDeclKind::Const {
+            continue;
+        }
+
+        for decl in &var.decls {
+            let (Pat::Ident(name), Some(init)) = (&decl.name, &decl.init) else {
+                continue;
+            };
+            if let Some(Lit::Bool(Bool { value, .. })) = init.unwrap_parens().as_lit() {
+                booleans.push((name.id.to_id(), *value));
+            }
+        }
+    }
+
+    booleans
+}
+
+/// Whether the call was already transformed. A `loadableGenerated` object
+/// without `modules` nor `webpack` is provided by the user instead, and is
+/// completed by the transform.
+fn has_loadable_generated(options: &ObjectLit, namespace: Option<&str>) -> bool {
+    let generated = match namespace {
+        None => find_prop(options, "loadableGenerated"),
+        Some(namespace) => match find_prop(options, namespace).map(Expr::unwrap_parens) {
+            Some(Expr::Object(namespaced)) => find_prop(namespaced, "loadableGenerated"),
+            _ => None,
+        },
+    };
+
+    match generated {
+        Some(Expr::Object(generated)) => {
+            find_prop(generated, "modules").is_some() || find_prop(generated, "webpack").is_some()
+        }
+        Some(_) => true,
+        None => false,
+    }
+}
+
+/// The `loadableGenerated` object provided by the user, see
+/// [has_loadable_generated].
+fn find_loadable_generated_mut<'a>(
+    options: &'a mut [PropOrSpread],
+    namespace: Option<&str>,
+) -> Option<&'a mut ObjectLit> {
+    let props = match namespace {
+        None => options,
+        Some(namespace) => match find_prop_mut(options, namespace)? {
+            Expr::Object(namespaced) => &mut namespaced.props[..],
+            _ => return None,
+        },
+    };
+
+    match find_prop_mut(props, "loadableGenerated")? {
+        Expr::Object(generated) => Some(generated),
+        _ => None,
+    }
+}
+
+/// Returns the value of the `name` property of an object literal, if it isn't
+/// computed.
+fn find_prop<'a>(object: &'a ObjectLit, name: &str) -> Option<&'a Expr> {
+    object.props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(KeyValueProp { key, value }) => match key {
+                PropName::Ident(IdentName { sym, .. }) if &**sym == name => Some(&**value),
+                PropName::Str(Str { value: key, .. }) if &**key == name => Some(&**value),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
     })
 }
 
-fn with_prop(key: &str, value: &str) -> PropOrSpread {
-    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
-        key: PropName::Str(key.into()),
-        value: Box::new(Expr::Lit(value.into())),
-    })))
+fn find_prop_mut<'a>(props: &'a mut [PropOrSpread], name: &str) -> Option<&'a mut Expr> {
+    props.iter_mut().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &mut **prop {
+            Prop::KeyValue(KeyValueProp { key, value }) => match key {
+                PropName::Ident(IdentName { sym, .. }) if &**sym == name => Some(&mut **value),
+                PropName::Str(Str { value: key, .. }) if &**key == name => Some(&mut **value),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+fn entry_report(filename: &FileName, entry: &DynamicEntry) -> String {
+    json!({
+        "filename": filename.to_string(),
+        "key": entry.key,
+        "specifier": entry.specifier,
+        "mode": entry.mode,
+        "layer": entry.layer,
+    })
+    .to_string()
+}
+
+fn is_client_only_comment(comment: &Comment) -> bool {
+    comment.text.trim() == "next-dynamic: client-only"
+}
+
+fn is_prop_named(prop: &PropOrSpread, name: &str) -> bool {
+    let PropOrSpread::Prop(prop) = prop else {
+        return false;
+    };
+    let key = match &**prop {
+        Prop::KeyValue(KeyValueProp { key, .. }) | Prop::Method(MethodProp { key, .. }) => key,
+        Prop::Shorthand(ident) => return &*ident.sym == name,
+        _ => return false,
+    };
+
+    match key {
+        PropName::Ident(IdentName { sym, .. }) => &**sym == name,
+        PropName::Str(Str { value, .. }) => &**value == name,
+        _ => false,
+    }
+}
+
+fn key_value_prop(key: &str, value: Box<Expr>) -> PropOrSpread {
+    let key = if Ident::verify_symbol(key).is_ok() {
+        PropName::Ident(IdentName::new(key.into(), DUMMY_SP))
+    } else {
+        PropName::Str(key.into())
+    };
+
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp { key, value })))
+}
+
+/// `modules: [...]`, or `modules: [() => ..., ...]` when `lazy` is set, and
+/// `module: ...` when a legacy module id is given.
+fn module_id_options(
+    module_ids: Vec<Expr>,
+    lazy: bool,
+    legacy_module_id: Option<Expr>,
+) -> Vec<PropOrSpread> {
+    // The legacy key isn't made lazy, as it predates lazy module ids.
+    let module_ids = if lazy {
+        module_ids
+            .into_iter()
+            .map(|module_id| {
+                Expr::Arrow(ArrowExpr {
+                    span: DUMMY_SP,
+                    params: vec![],
+                    body: Box::new(BlockStmtOrExpr::Expr(Box::new(module_id))),
+                    is_async: false,
+                    is_generator: false,
+                    ..Default::default()
+                })
+            })
+            .collect()
+    } else {
+        module_ids
+    };
+
+    let mut props = vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(IdentName::new("modules".into(), DUMMY_SP)),
+        value: Box::new(Expr::Array(ArrayLit {
+            elems: module_id_elems(module_ids),
+            span: DUMMY_SP,
+        })),
+    })))];
+    if let Some(module_id) = legacy_module_id {
+        props.push(key_value_prop("module", Box::new(module_id)));
+    }
+
+    props
+}
+
+fn webpack_options(module_ids: Vec<Expr>) -> Vec<PropOrSpread> {
+    vec![PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Ident(IdentName::new("webpack".into(), DUMMY_SP)),
+        value: Box::new(Expr::Arrow(ArrowExpr {
+            params: vec![],
+            body: Box::new(BlockStmtOrExpr::Expr(Box::new(Expr::Array(ArrayLit {
+                elems: module_id_elems(module_ids),
+                span: DUMMY_SP,
+            })))),
+            is_async: false,
+            is_generator: false,
+            span: DUMMY_SP,
+            ..Default::default()
+        })),
+    })))]
+}
+
+fn module_id_elems(module_ids: Vec<Expr>) -> Vec<Option<ExprOrSpread>> {
+    module_ids
+        .into_iter()
+        .map(|module_id| {
+            Some(ExprOrSpread {
+                expr: Box::new(module_id),
+                spread: None,
+            })
+        })
+        .collect()
+}
+
+impl<C: Comments> NextDynamicPatcher<C> {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        is_development: bool,
+        is_server_compiler: bool,
+        is_react_server_layer: bool,
+        prefer_esm: bool,
+        mode: NextDynamicMode,
+        filename: Arc<FileName>,
+        pages_or_app_dir: Option<PathBuf>,
+        config: Config,
+        comments: C,
+    ) -> Self {
+        NextDynamicPatcher {
+            is_development,
+            is_server_compiler,
+            is_react_server_layer,
+            prefer_esm,
+            pages_or_app_dir,
+            project_root: None,
+            filename,
+            config,
+            comments,
+            dynamic_bindings: vec![],
+            reassigned_bindings: vec![],
+            const_aliases: vec![],
+            imported_bindings: vec![],
+            module_booleans: vec![],
+            loader_helpers: vec![],
+            is_loadable_disabled: false,
+            is_next_dynamic_first_arg: false,
+            dynamically_imported_specifiers: vec![],
+            import_attributes: vec![],
+            preload_specifiers: vec![],
+            client_only_calls: vec![],
+            reexports_dynamic: false,
+            hashed_keys: vec![],
+            reported_missing_project_dir: false,
+            state: match mode {
+                NextDynamicMode::Webpack => NextDynamicPatcherState::Webpack,
+                NextDynamicMode::Turbopack {
+                    dynamic_transition_name,
+                    import_attribute_keys,
+                    module_id_export,
+                    transition_import_phase,
+                } => NextDynamicPatcherState::Turbopack {
+                    dynamic_transition_name,
+                    import_attribute_keys,
+                    transition_import_phase,
+                    module_id_export: module_id_export
+                        .map(Atom::from)
+                        .unwrap_or_else(|| "__turbopack_module_id__".into()),
+                    imports: vec![],
+                },
+            },
+            entries: vec![],
+        }
+    }
+
+    fn is_dynamic_source(&self, src: &Atom) -> bool {
+        self.config.is_dynamic_source(src)
+    }
+
+    fn has_project_dir(&self) -> bool {
+        self.project_root.is_some()
+            || self
+                .pages_or_app_dir
+                .as_deref()
+                .and_then(Path::parent)
+                .is_some()
+    }
+
+    /// The current module's path relative to the project directory.
+    fn rel_filename(&self) -> String {
+        let mapper = self.config.custom_filename_mapper.as_deref();
+
+        if let Some(project_root) = &self.project_root {
+            if let (FileName::Custom(name), Some(mapper)) = (&*self.filename, mapper) {
+                if let Some(mapped) = mapper(name.as_str()) {
+                    return mapped;
+                }
+            }
+
+            let file = match &*self.filename {
+                FileName::Custom(name) => name.clone(),
+                file => file.to_string(),
+            };
+            // Only at a path boundary, e.g. not in `vfs:/app-admin/page.js` for
+            // `vfs:/app`.
+            return match file.strip_prefix(project_root.trim_end_matches('/')) {
+                Some(rel) if rel.is_empty() || rel.starts_with('/') => {
+                    rel.trim_start_matches('/').to_string()
+                }
+                _ => file,
+            };
+        }
+
+        if let FileName::Real(file) = &*self.filename {
+            let closest_base_dir = self
+                .config
+                .base_dirs
+                .iter()
+                .filter(|dir| file.starts_with(dir))
+                .max_by_key(|dir| dir.components().count());
+            if let Some(base_dir) = closest_base_dir {
+                return rel_filename(Some(base_dir.as_path()), &self.filename, mapper);
+            }
+        }
+
+        let project_dir = self.pages_or_app_dir.as_deref().and_then(Path::parent);
+        rel_filename(project_dir, &self.filename, mapper)
+    }
+
+    /// Returns the specifier imported by the helper called in `loader`, if it
+    /// is of the form `() => helper()`.
+    fn resolve_loader_helper(&self, loader: &Expr) -> Option<(String, Span)> {
+        let Expr::Arrow(ArrowExpr { body, .. }) = loader.unwrap_parens() else {
+            return None;
+        };
+        let BlockStmtOrExpr::Expr(body) = &**body else {
+            return None;
+        };
+        let Expr::Call(CallExpr {
+            callee: Callee::Expr(callee),
+            ..
+        }) = unwrap_ts_and_parens(body)
+        else {
+            return None;
+        };
+        let Expr::Ident(helper) = &**callee else {
+            return None;
+        };
+
+        let (_, specifier) = self
+            .loader_helpers
+            .iter()
+            .find(|(id, _)| *id == helper.to_id())?;
+        if specifier.is_none() {
+            self.emit_warning(
+                helper.span,
+                &format!(
+                    "next/dynamic can't find the module imported by `{}`. Helpers are only \
+                     followed when they return `import()` directly.",
+                    helper.sym
+                ),
+            );
+        }
+
+        specifier.clone()
+    }
+
+    fn report_unused_imports(&self, items: &[ModuleItem]) {
+        let mut referenced = ReferencedIdents::default();
+        items.visit_with(&mut referenced);
+
+        for item in items {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                src, specifiers, ..
+            })) = item
+            else {
+                continue;
+            };
+            if !self.is_dynamic_source(&src.value) {
+                continue;
+            }
+            for specifier in specifiers {
+                if let ImportSpecifier::Default(ImportDefaultSpecifier { local, span }) = specifier
+                {
+                    if !referenced.ids.contains(&local.to_id()) {
+                        self.emit_note(
+                            *span,
+                            &format!(
+                                "`{}` is imported from '{}' but never used. The import can be \
+                                 removed.",
+                                local.sym, src.value
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// withTracking(dynamic)(() => import('../components/hello'))
+    fn report_wrapped_callee(&self, callee: &Callee) {
+        let Callee::Expr(callee) = callee else {
+            return;
+        };
+        let Expr::Call(CallExpr {
+            callee: Callee::Expr(wrapper),
+            args,
+            span,
+            ..
+        }) = unwrap_ts_and_parens(callee)
+        else {
+            return;
+        };
+        let wraps_dynamic = args.iter().any(|arg| match &*arg.expr {
+            Expr::Ident(ident) => self.dynamic_bindings.contains(&ident.to_id()),
+            _ => false,
+        });
+        if !wraps_dynamic {
+            return;
+        }
+
+        let wrapper = match &**wrapper {
+            Expr::Ident(ident) => format!("`{}`", ident.sym),
+            _ => "a helper".to_string(),
+        };
+        self.emit_note(
+            *span,
+            &format!(
+                "`next/dynamic` is called through the function returned by {wrapper}. The call \
+                 isn't transformed, so its modules aren't added to the React Loadable Manifest."
+            ),
+        );
+    }
+
+    fn report_ssr_option_usage(&self, span: Span, ssr: Option<bool>) {
+        let message = match ssr {
+            Some(true) => {
+                "`ssr: true` is the default for next/dynamic and can be removed.\nRead more: \
+                 https://nextjs.org/docs/app/building-your-application/optimizing/lazy-loading"
+            }
+            Some(false) => {
+                "`ssr: false` disables server-side rendering of this component, make sure this is \
+                 still needed.\nRead more: \
+                 https://nextjs.org/docs/app/building-your-application/optimizing/lazy-loading#skipping-ssr"
+            }
+            None => return,
+        };
+
+        self.emit_note(span, message);
+    }
+
+    fn emit_error(&self, span: Span, msg: &str, code: &str) {
+        self.emit(Level::Error, span, msg, Some(code));
+    }
+
+    fn emit_warning(&self, span: Span, msg: &str) {
+        self.emit(Level::Warning, span, msg, None);
+    }
+
+    fn emit_note(&self, span: Span, msg: &str) {
+        self.emit(Level::Note, span, msg, None);
+    }
+
+    /// Reports every diagnostic of the transform, to
+    /// [Config::diagnostics_buffer] if set. Warnings are reported as errors with
+    /// [Config::treat_warnings_as_errors].
+    fn emit(&self, level: Level, span: Span, msg: &str, code: Option<&str>) {
+        let level = match level {
+            Level::Warning if self.config.treat_warnings_as_errors => Level::Error,
+            level => level,
+        };
+        if let Some(buffer) = &self.config.diagnostics_buffer {
+            buffer.lock().unwrap().push(FileDiagnostic {
+                filename: self.filename.clone(),
+                span,
+                level,
+                code: code.map(String::from),
+                message: msg.to_string(),
+            });
+            return;
+        }
+
+        HANDLER.with(|handler| {
+            let mut diagnostic = DiagnosticBuilder::new(handler, level, msg);
+            diagnostic.set_span(span);
+            if let Some(code) = code {
+                diagnostic.code(DiagnosticId::Error(code.into()));
+            }
+            diagnostic.emit()
+        });
+    }
+
+    fn report_router_boundary_crossing(&self, specifier: &str, span: Span) {
+        let (Some(router_dir), FileName::Real(file)) =
+            (self.pages_or_app_dir.as_deref(), &*self.filename)
+        else {
+            return;
+        };
+        let (router, other_router) = match router_dir.file_name().and_then(|name| name.to_str()) {
+            Some("app") => ("app", "pages"),
+            Some("pages") => ("pages", "app"),
+            _ => return,
+        };
+        if !specifier.starts_with('.') || !file.starts_with(router_dir) {
+            return;
+        }
+
+        let Some(file_dir) = file.parent() else {
+            return;
+        };
+        let imported = normalize_path(&file_dir.join(specifier));
+        if imported.starts_with(router_dir.with_file_name(other_router)) {
+            self.emit_warning(
+                span,
+                &format!(
+                    "next/dynamic imports a module of the `{other_router}` directory from the \
+                     `{router}` directory. Its key may not resolve in the React Loadable Manifest."
+                ),
+            );
+        }
+    }
+
+    /// The `require.resolveWeak` function, or its replacement.
+    fn resolve_weak_callee(&self) -> Ident {
+        let callee = self
+            .config
+            .resolve_weak_callee
+            .as_deref()
+            .unwrap_or("require.resolveWeak");
+
+        Ident::new(callee.into(), DUMMY_SP, Default::default())
+    }
+
+    /// Whether the call is preceded by a `next-dynamic: client-only` comment,
+    /// which makes it behave like an `ssr: false` call.
+    fn has_client_only_comment(&self, span: Span) -> bool {
+        self.client_only_calls.contains(&span)
+            || self.comments.with_leading(span.lo, |comments| {
+                comments.iter().any(is_client_only_comment)
+            })
+    }
+
+    fn rewrite_specifier(&self, (specifier, span): (String, Span)) -> (String, Span) {
+        let rewritten = self
+            .config
+            .specifier_rewriter
+            .as_deref()
+            .and_then(|rewriter| rewriter(&specifier));
+
+        (rewritten.unwrap_or(specifier), span)
+    }
+
+    fn emit_argument_count_diagnostic(&self, span: Span, msg: &str, code: &str) {
+        let level = match self.config.argument_count_severity {
+            ArgumentCountSeverity::Error => Level::Error,
+            ArgumentCountSeverity::Warning => Level::Warning,
+        };
+        self.emit(level, span, msg, Some(code));
+    }
+
+    fn mode(&self) -> DynamicEntryMode {
+        match self.state {
+            NextDynamicPatcherState::Webpack => DynamicEntryMode::Webpack,
+            NextDynamicPatcherState::Turbopack { .. } => DynamicEntryMode::Turbopack,
+        }
+    }
+
+    fn layer(&self) -> DynamicEntryLayer {
+        match (self.is_server_compiler, self.is_react_server_layer) {
+            (false, _) => DynamicEntryLayer::Client,
+            (true, false) => DynamicEntryLayer::Ssr,
+            (true, true) => DynamicEntryLayer::ReactServer,
+        }
+    }
+
+    fn maybe_add_preload_exports(&mut self, items: &mut Vec<ModuleItem>) {
+        if self.preload_specifiers.is_empty() {
+            return;
+        }
+
+        // The bindings and exports of the module, e.g. a user-declared
+        // `__preload_hello`.
+        let mut names: Vec<String> = collect_decls::<Id, _>(&*items)
+            .into_iter()
+            .map(|(sym, _)| sym.to_string())
+            .collect();
+        for item in items.iter() {
+            if let ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(NamedExport {
+                specifiers, ..
+            })) = item
+            {
+                for specifier in specifiers {
+                    if let ExportSpecifier::Named(ExportNamedSpecifier { orig, exported, .. }) =
+                        specifier
+                    {
+                        names.push(exported.as_ref().unwrap_or(orig).atom().to_string());
+                    }
+                }
+            }
+        }
+
+        for specifier in std::mem::take(&mut self.preload_specifiers) {
+            let mut name = preload_export_name(&specifier);
+            if names.contains(&name) {
+                let mut i = 1;
+                while names.contains(&format!("{name}_{i}")) {
+                    i += 1;
+                }
+                name = format!("{name}_{i}");
+            }
+
+            // export const __preload_hello = () => import('../components/hello')
+            items.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                span: DUMMY_SP,
+                decl: Decl::Var(Box::new(VarDecl {
+                    span: DUMMY_SP,
+                    kind: VarDeclKind::Const,
+                    decls: vec![VarDeclarator {
+                        span: DUMMY_SP,
+                        name: Pat::Ident(
+                            Ident::new(name.as_str().into(), DUMMY_SP, Default::default()).into(),
+                        ),
+                        init: Some(Box::new(quote!(
+                            "() => import($specifier)" as Expr,
+                            specifier: Expr = specifier.into(),
+                        ))),
+                        definite: false,
+                    }],
+                    ..Default::default()
+                })),
+            })));
+            names.push(name);
+        }
+    }
+
+    fn maybe_add_dynamically_imported_specifier(&mut self, items: &mut Vec<ModuleItem>) {
+        if self.config.dry_run {
+            return;
+        }
+
+        let NextDynamicPatcherState::Turbopack {
+            dynamic_transition_name,
+            import_attribute_keys,
+            module_id_export,
+            transition_import_phase,
+            imports,
+        } = &mut self.state
+        else {
+            return;
+        };
+
+        let mut new_items = Vec::with_capacity(imports.len());
+
+        // The imports are added in the order their calls appear in, so that the
+        // output is reproducible.
+        for import in std::mem::take(imports) {
+            match import {
+                TurbopackImport::DevelopmentTransition {
+                    id_ident,
+                    chunks_ident,
+                    specifier,
+                } => {
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers: vec![
+                            ImportSpecifier::Default(ImportDefaultSpecifier {
+                                span: DUMMY_SP,
+                                local: id_ident,
+                            }),
+                            ImportSpecifier::Named(ImportNamedSpecifier {
+                                span: DUMMY_SP,
+                                local: chunks_ident,
+                                imported: Some(
+                                    Ident::new("chunks".into(), DUMMY_SP, Default::default())
+                                        .into(),
+                                ),
+                                is_type_only: false,
+                            }),
+                        ],
+                        src: Box::new(specifier.into()),
+                        type_only: false,
+                        // The transition should return both the target module's id
+                        // and the chunks it needs to run.
+                        with: Some(with_transition(
+                            import_attribute_keys,
+                            dynamic_transition_name,
+                        )),
+                        phase: *transition_import_phase,
+                    })));
+                }
+                TurbopackImport::DevelopmentId {
+                    id_ident,
+                    specifier,
+                } => {
+                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
+                    // identifier into the imported module's id.
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local: id_ident,
+                            imported: Some(
+                                Ident::new(module_id_export.clone(), DUMMY_SP, Default::default())
+                                    .into(),
+                            ),
+                            is_type_only: false,
+                        })],
+                        src: Box::new(specifier.into()),
+                        type_only: false,
+                        // We don't want this import to cause the imported module to be considered
+                        // for chunking through this import; we only need
+                        // the module id.
+                        with: Some(with_chunking_type(import_attribute_keys, "none")),
+                        phase: Default::default(),
+                    })));
+                }
+                TurbopackImport::BuildTransition {
+                    id_ident,
+                    specifier,
+                } => {
+                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
+                    // identifier into the imported module's id.
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local: id_ident,
+                            imported: Some(
+                                Ident::new(module_id_export.clone(), DUMMY_SP, Default::default())
+                                    .into(),
+                            ),
+                            is_type_only: false,
+                        })],
+                        src: Box::new(specifier.into()),
+                        type_only: false,
+                        // The transition should make sure the imported module ends up in the
+                        // dynamic manifest.
+                        with: Some(with_transition(
+                            import_attribute_keys,
+                            dynamic_transition_name,
+                        )),
+                        phase: *transition_import_phase,
+                    })));
+                }
+                TurbopackImport::BuildId {
+                    id_ident,
+                    specifier,
+                } => {
+                    // Turbopack will automatically transform the imported `__turbopack_module_id__`
+                    // identifier into the imported module's id.
+                    new_items.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                        span: DUMMY_SP,
+                        specifiers: vec![ImportSpecifier::Named(ImportNamedSpecifier {
+                            span: DUMMY_SP,
+                            local: id_ident,
+                            imported: Some(
+                                Ident::new(module_id_export.clone(), DUMMY_SP, Default::default())
+                                    .into(),
+                            ),
+                            is_type_only: false,
+                        })],
+                        src: Box::new(specifier.into()),
+                        type_only: false,
+                        // We don't want this import to cause the imported module to be considered
+                        // for chunking through this import; we only need
+                        // the module id.
+                        with: Some(with_chunking_type(import_attribute_keys, "none")),
+                        phase: Default::default(),
+                    })));
+                }
+            }
+        }
+
+        for item in &mut new_items {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                src,
+                with: Some(with),
+                ..
+            })) = item
+            else {
+                continue;
+            };
+            let Some((_, attributes)) = self
+                .import_attributes
+                .iter()
+                .find(|(specifier, _)| &*src.value == specifier.as_str())
+            else {
+                continue;
+            };
+
+            // The generated attributes take precedence.
+            for (key, value) in attributes {
+                if find_prop(with, key).is_none() {
+                    with.props.push(with_prop(key, value));
+                }
+            }
+        }
+
+        new_items.append(items);
+
+        std::mem::swap(&mut new_items, items)
+    }
+}
+
+fn exec_expr_when_resolve_weak_available(resolve_weak_callee: &Ident, expr: &Expr) -> Expr {
+    let undefined_str_literal = Expr::Lit(Lit::Str(Str {
+        span: DUMMY_SP,
+        value: "undefined".into(),
+        raw: None,
+    }));
+
+    let typeof_expr = Expr::Unary(UnaryExpr {
+        span: DUMMY_SP,
+        op: UnaryOp::TypeOf, // 'typeof' operator
+        arg: Box::new(Expr::Ident(resolve_weak_callee.clone())),
+    });
+
+    // typeof require.resolveWeak !== 'undefined' && <expression>
+    Expr::Bin(BinExpr {
+        span: DUMMY_SP,
+        left: Box::new(Expr::Bin(BinExpr {
+            span: DUMMY_SP,
+            op: op!("!=="),
+            left: Box::new(typeof_expr),
+            right: Box::new(undefined_str_literal),
+        })),
+        op: op!("&&"),
+        right: Box::new(expr.clone()),
+    })
+}
+
+fn rel_filename(
+    base: Option<&Path>,
+    file: &FileName,
+    custom_filename_mapper: Option<&FileNameMapper>,
+) -> String {
+    if let (FileName::Custom(name), Some(mapper)) = (file, custom_filename_mapper) {
+        if let Some(mapped) = mapper(name.as_str()) {
+            return mapped;
+        }
+    }
+
+    let base = match base {
+        Some(v) => v,
+        None => return file.to_string(),
+    };
+
+    let file = match file {
+        FileName::Real(v) => v,
+        _ => {
+            return file.to_string();
+        }
+    };
+
+    let rel_path = diff_paths(file, base);
+
+    let rel_path = match rel_path {
+        Some(v) => v,
+        None => return file.display().to_string(),
+    };
+
+    rel_path.display().to_string()
+}
+
+/// Resolves the `.` and `..` components of `path` without accessing the file
+/// system.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+
+    normalized
+}
+
+/// Leading `..` segments are kept, as there's nothing to collapse them into.
+fn canonical_rel_path(rel_filename: &str) -> String {
+    let mut segments: Vec<&str> = vec![];
+    for segment in rel_filename.split(['/', '\\']) {
+        match segment {
+            "" | "." => {}
+            ".." if segments.last().is_some_and(|last| *last != "..") => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let root = if rel_filename.starts_with('/') {
+        "/"
+    } else {
+        ""
+    };
+    format!("{root}{}", segments.join("/"))
+}
+
+fn url_path(rel_filename: &str) -> String {
+    format!(
+        "/{}",
+        rel_filename.replace('\\', "/").trim_start_matches('/')
+    )
+}
+
+fn normalize_specifier(specifier: &str) -> String {
+    if specifier.starts_with('.') || specifier.starts_with('/') {
+        specifier.to_string()
+    } else {
+        format!("./{specifier}")
+    }
+}
+
+/// Keeps the start of `specifier`, followed by `~` and 8 characters of its hash,
+/// within `max_length` characters.
+fn shorten_specifier(specifier: &str, max_length: usize) -> String {
+    let hash = &hash_module_key(specifier)[..8];
+    let start: String = specifier
+        .chars()
+        .take(max_length.saturating_sub(hash.len() + 1))
+        .collect();
+
+    format!("{start}~{hash}")
+}
+
+/// An opaque but deterministic replacement of a React Loadable Manifest key.
+fn hash_module_key(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hex_encode(hasher.finalize())
+}
+
+/// The generated `id` ident is private, but when the project directory is known
+/// we suffix it with the current module's relative path so that the output of
+/// Turbopack builds is easier to debug.
+fn turbopack_id_ident_name(prefix: Option<&str>, rel_filename: Option<&str>) -> String {
+    let prefix = prefix.unwrap_or("id_");
+    let Some(rel_filename) = rel_filename else {
+        return prefix.trim_end_matches('_').into();
+    };
+
+    let suffix: String = rel_filename
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+
+    format!("{prefix}{suffix}")
+}
+
+fn with_chunking_type(keys: &ImportAttributeKeys, chunking_type: &str) -> Box<ObjectLit> {
+    with_clause(&[(keys.chunking_type.as_str(), chunking_type)])
+}
+
+fn with_transition(keys: &ImportAttributeKeys, transition_name: &str) -> Box<ObjectLit> {
+    with_clause(&[(keys.transition.as_str(), transition_name)])
+}
+
+fn with_clause<'a>(entries: impl IntoIterator<Item = &'a (&'a str, &'a str)>) -> Box<ObjectLit> {
+    Box::new(ObjectLit {
+        span: DUMMY_SP,
+        props: entries.into_iter().map(|(k, v)| with_prop(k, v)).collect(),
+    })
+}
+
+fn with_prop(key: &str, value: &str) -> PropOrSpread {
+    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+        key: PropName::Str(key.into()),
+        value: Box::new(Expr::Lit(value.into())),
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::{
+            comments::NoopComments,
+            errors::{Emitter, Handler},
+            EqIgnoreSpan, FilePathMapping, SourceMap, GLOBALS,
+        },
+        ecma::parser::{parse_file_as_module, EsSyntax, Syntax},
+    };
+
+    use super::*;
+
+    type CollectedDiagnostic = (Level, String, Option<DiagnosticId>);
+
+    #[derive(Clone, Default)]
+    struct CollectedDiagnostics(Arc<Mutex<Vec<CollectedDiagnostic>>>);
+
+    impl Emitter for CollectedDiagnostics {
+        fn emit(&mut self, db: &DiagnosticBuilder<'_>) {
+            let message = db.message();
+            self.0
+                .lock()
+                .unwrap()
+                .push((db.level, message, db.code.clone()));
+        }
+    }
+
+    fn parse(src: &str) -> Module {
+        let cm = SourceMap::new(FilePathMapping::empty());
+        let fm = cm.new_source_file(FileName::Anon.into(), src.to_string());
+
+        parse_file_as_module(
+            &fm,
+            Syntax::Es(EsSyntax {
+                jsx: true,
+                ..Default::default()
+            }),
+            Default::default(),
+            None,
+            &mut vec![],
+        )
+        .unwrap()
+    }
+
+    fn transform(
+        is_development: bool,
+        is_server_compiler: bool,
+        mode: NextDynamicMode,
+        src: &str,
+    ) -> DynamicTransformResult {
+        transform_with_config(
+            is_development,
+            is_server_compiler,
+            mode,
+            Default::default(),
+            src,
+        )
+        .0
+    }
+
+    fn transform_with_config(
+        is_development: bool,
+        is_server_compiler: bool,
+        mode: NextDynamicMode,
+        config: Config,
+        src: &str,
+    ) -> (DynamicTransformResult, Vec<(Level, String)>) {
+        let (result, diagnostics) =
+            transform_with_diagnostics(is_development, is_server_compiler, mode, config, src);
+        let diagnostics = diagnostics
+            .into_iter()
+            .map(|(level, message, _)| (level, message))
+            .collect();
+
+        (result, diagnostics)
+    }
+
+    fn transform_with_diagnostics(
+        is_development: bool,
+        is_server_compiler: bool,
+        mode: NextDynamicMode,
+        config: Config,
+        src: &str,
+    ) -> (
+        DynamicTransformResult,
+        Vec<(Level, String, Option<DiagnosticId>)>,
+    ) {
+        let diagnostics = CollectedDiagnostics::default();
+        let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+
+        let result = GLOBALS.set(&Default::default(), || {
+            HANDLER.set(&handler, || {
+                next_dynamic_with_entries(
+                    is_development,
+                    is_server_compiler,
+                    false,
+                    false,
+                    mode,
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                    Some("/some-project/src".into()),
+                    config,
+                    NoopComments,
+                    parse(src),
+                )
+            })
+        });
+
+        let diagnostics = diagnostics.0.lock().unwrap().clone();
+        (result, diagnostics)
+    }
+
+    #[test]
+    fn should_inspect_the_final_options() {
+        let keys = Arc::new(Mutex::new(vec![]));
+        let inspected_keys = keys.clone();
+
+        transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                options_inspector: Some(Arc::new(move |options: &ObjectLit| {
+                    inspected_keys.lock().unwrap().push(
+                        options
+                            .props
+                            .iter()
+                            .filter_map(|prop| match prop {
+                                PropOrSpread::Prop(prop) => match &**prop {
+                                    Prop::KeyValue(KeyValueProp {
+                                        key: PropName::Ident(key),
+                                        ..
+                                    }) => Some(key.sym.to_string()),
+                                    _ => None,
+                                },
+                                PropOrSpread::Spread(_) => None,
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })),
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'), { ssr: false })
+            "#,
+        );
+
+        assert_eq!(
+            *keys.lock().unwrap(),
+            vec![vec!["loadableGenerated".to_string(), "ssr".to_string()]]
+        );
+    }
+
+    #[test]
+    fn should_keep_the_order_of_options() {
+        // A xorshift generator, so that the shuffled orders are reproducible.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        for _ in 0..8 {
+            let mut keys = (0..16).map(|i| format!("option{i}")).collect::<Vec<_>>();
+            for i in (1..keys.len()).rev() {
+                keys.swap(i, (next() % (i as u64 + 1)) as usize);
+            }
+            let src = format!(
+                "import dynamic from 'next/dynamic'\nconst Hello = dynamic(() => \
+                 import('../components/hello'), {{ {} }})",
+                keys.iter()
+                    .map(|key| format!("{key}: true"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+
+            let mut expected = vec!["loadableGenerated".to_string()];
+            expected.extend(keys.iter().cloned());
+            for _ in 0..2 {
+                let inspected = Arc::new(Mutex::new(vec![]));
+                let inspected_keys = inspected.clone();
+                transform_with_config(
+                    true,
+                    false,
+                    NextDynamicMode::Webpack,
+                    Config {
+                        options_inspector: Some(Arc::new(move |options: &ObjectLit| {
+                            let mut keys = inspected_keys.lock().unwrap();
+                            keys.extend(options.props.iter().filter_map(|prop| match prop {
+                                PropOrSpread::Prop(prop) => match &**prop {
+                                    Prop::KeyValue(KeyValueProp {
+                                        key: PropName::Ident(key),
+                                        ..
+                                    }) => Some(key.sym.to_string()),
+                                    _ => None,
+                                },
+                                PropOrSpread::Spread(_) => None,
+                            }))
+                        })),
+                        ..Default::default()
+                    },
+                    &src,
+                );
+
+                assert_eq!(*inspected.lock().unwrap(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn should_canonicalize_rel_paths() {
+        assert_eq!(
+            canonical_rel_path("./pages/../app/./(group)//page.js"),
+            "app/(group)/page.js"
+        );
+        assert_eq!(
+            canonical_rel_path("../../shared/./hello.js"),
+            "../../shared/hello.js"
+        );
+        assert_eq!(
+            canonical_rel_path("app\\..\\pages\\index.js"),
+            "pages/index.js"
+        );
+        assert_eq!(
+            canonical_rel_path("/some-project/src/../app/page.js"),
+            "/some-project/app/page.js"
+        );
+    }
+
+    #[test]
+    fn should_note_wrapped_callees() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                report_wrapped_callees: true,
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = withTracking(dynamic)(() => import('../components/hello'))
+            const World = withTracking(other)(() => import('../components/world'))
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Note,
+                "`next/dynamic` is called through the function returned by `withTracking`. The \
+                 call isn't transformed, so its modules aren't added to the React Loadable \
+                 Manifest."
+                    .to_string()
+            )]
+        );
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn should_note_unused_imports() {
+        let config = Config {
+            report_unused_imports: true,
+            ..Default::default()
+        };
+
+        let (_, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            config.clone(),
+            r#"
+            import dynamic from 'next/dynamic'
+
+            export const Hello = () => null
+            "#,
+        );
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Note,
+                "`dynamic` is imported from 'next/dynamic' but never used. The import can be \
+                 removed."
+                    .to_string()
+            )]
+        );
+
+        let (_, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            config,
+            r#"
+            import dynamic from 'next/dynamic'
+
+            export const Hello = dynamic(() => import('../components/hello'))
+            "#,
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn should_report_unresolved_specifiers() {
+        let (result, diagnostics) = transform_with_diagnostics(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                specifier_resolver: Some(Arc::new(|filename: &FileName, specifier: &str| {
+                    assert_eq!(filename.to_string(), "/some-project/src/some-file.js");
+                    specifier != "../components/helo"
+                })),
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const Typo = dynamic(() => import('../components/helo'))
+            const Package = dynamic(() => import('some-package'))
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Error,
+                "next/dynamic can't resolve `../components/helo` from this module".to_string(),
+                Some(DiagnosticId::Error(codes::UNRESOLVED_SPECIFIER.into()))
+            )]
+        );
+        assert_eq!(result.entries.len(), 3);
+    }
+
+    #[test]
+    fn should_emit_warnings_unless_treated_as_errors() {
+        let cases = [
+            (
+                "const Hello = dynamic(() => import(`../components/${name}`))",
+                "next/dynamic can't preload modules imported with a non-static specifier. Pass a \
+                 string literal to `import()` instead.",
+            ),
+            (
+                "const Hello = dynamic(() => import(new URL('../components/hello', \
+                 import.meta.url)))",
+                "next/dynamic can't add modules imported with `new URL(..., import.meta.url)` to \
+                 the React Loadable Manifest. Pass a string literal to `import()` instead.",
+            ),
+            (
+                "const Hello = dynamic(() => import('../components/hello'), { get ssr() { return \
+                 false } })",
+                "next/dynamic can't read the `ssr` option of a getter, setter or method. Use \
+                 `ssr: true` or `ssr: false` instead.",
+            ),
+            (
+                "import loadHello from '../components/load-hello'\nconst Hello = \
+                 dynamic(loadHello)",
+                "next/dynamic can't find the module loaded by `loadHello`, which is imported from \
+                 '../components/load-hello'. No key can be generated for the React Loadable \
+                 Manifest. Pass `() => import('...')` to `dynamic()` instead.",
+            ),
+            (
+                "const Hello = dynamic(() => import('../components/hello'), { loading: async () \
+                 => <p>Loading...</p> })",
+                "next/dynamic renders the `loading` option as a React component, which can't be \
+                 an async function.",
+            ),
+            (
+                "const Hello = dynamic(() => isEnabled && import('../components/hello'))",
+                "next/dynamic loads this module conditionally, which may not match the React \
+                 Loadable Manifest. Import it unconditionally and render it conditionally instead.",
+            ),
+        ];
+
+        for (src, message) in cases {
+            let src = format!("import dynamic from 'next/dynamic'\n{src}");
+            for treat_warnings_as_errors in [false, true] {
+                let (_, diagnostics) = transform_with_config(
+                    true,
+                    false,
+                    NextDynamicMode::Webpack,
+                    Config {
+                        report_non_static_specifiers: true,
+                        treat_warnings_as_errors,
+                        ..Default::default()
+                    },
+                    &src,
+                );
+
+                let level = if treat_warnings_as_errors {
+                    Level::Error
+                } else {
+                    Level::Warning
+                };
+                assert_eq!(diagnostics, vec![(level, message.to_string())], "{src}");
+            }
+        }
+    }
+
+    #[test]
+    fn should_warn_about_router_boundary_crossings() {
+        let diagnostics = CollectedDiagnostics::default();
+        let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+
+        GLOBALS.set(&Default::default(), || {
+            HANDLER.set(&handler, || {
+                next_dynamic_with_entries(
+                    true,
+                    false,
+                    false,
+                    false,
+                    NextDynamicMode::Webpack,
+                    FileName::Real(PathBuf::from("/some-project/src/app/page.js")).into(),
+                    Some("/some-project/src/app".into()),
+                    Config {
+                        report_router_boundary_crossings: true,
+                        ..Default::default()
+                    },
+                    NoopComments,
+                    parse(
+                        "import dynamic from 'next/dynamic'
+const Hello = dynamic(() => import('../pages/hello'))",
+                    ),
+                )
+            })
+        });
+
+        assert_eq!(
+            *diagnostics.0.lock().unwrap(),
+            vec![(
+                Level::Warning,
+                "next/dynamic imports a module of the `pages` directory from the `app` directory. \
+                 Its key may not resolve in the React Loadable Manifest."
+                    .to_string(),
+                None
+            )]
+        );
+    }
+
+    #[test]
+    fn should_buffer_warnings_by_file() {
+        let buffer = Arc::new(DiagnosticsBuffer::default());
+        let diagnostics = CollectedDiagnostics::default();
+        let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+
+        for (filename, src) in [
+            (
+                "/some-project/src/first.js",
+                "import dynamic from 'next/dynamic'\nconst Hello = dynamic(() => import(name))",
+            ),
+            (
+                "/some-project/src/second.js",
+                "import dynamic from 'next/dynamic'\nconst World = dynamic(() => \
+                 Promise.all([import('../components/world'), import('../components/other')]))",
+            ),
+        ] {
+            GLOBALS.set(&Default::default(), || {
+                HANDLER.set(&handler, || {
+                    next_dynamic_with_entries(
+                        true,
+                        false,
+                        false,
+                        false,
+                        NextDynamicMode::Webpack,
+                        FileName::Real(PathBuf::from(filename)).into(),
+                        Some("/some-project/src".into()),
+                        Config {
+                            report_non_static_specifiers: true,
+                            diagnostics_buffer: Some(buffer.clone()),
+                            ..Default::default()
+                        },
+                        NoopComments,
+                        parse(src),
+                    )
+                })
+            });
+        }
+
+        let buffered = buffer.lock().unwrap();
+        assert_eq!(
+            buffered
+                .iter()
+                .map(|diagnostic| (diagnostic.filename.to_string(), diagnostic.message.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/some-project/src/first.js".to_string(),
+                    "next/dynamic can't preload modules imported with a non-static specifier. \
+                     Pass a string literal to `import()` instead."
+                ),
+                (
+                    "/some-project/src/second.js".to_string(),
+                    "next/dynamic loaders should import a single module. All the modules imported \
+                     by this loader are added to `loadableGenerated`."
+                )
+            ]
+        );
+        assert!(diagnostics.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_buffer_diagnostics_of_each_level() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const Empty = dynamic(() => import(''))
+            const Hello = dynamic(() => import(name))
+            const World = dynamic(() => import('../components/world'), {}, {})
+            const Other = dynamic(() => import('../components/other'), { ...options })
+            "#;
+
+        for treat_warnings_as_errors in [false, true] {
+            let buffer = Arc::new(DiagnosticsBuffer::default());
+            let (_, diagnostics) = transform_with_config(
+                true,
+                false,
+                NextDynamicMode::Webpack,
+                Config {
+                    argument_count_severity: ArgumentCountSeverity::Warning,
+                    report_non_static_specifiers: true,
+                    treat_warnings_as_errors,
+                    diagnostics_buffer: Some(buffer.clone()),
+                    ..Default::default()
+                },
+                src,
+            );
+            assert!(diagnostics.is_empty());
+
+            let warning = if treat_warnings_as_errors {
+                Level::Error
+            } else {
+                Level::Warning
+            };
+            let buffered = buffer.lock().unwrap();
+            assert_eq!(
+                buffered
+                    .iter()
+                    .map(|diagnostic| (
+                        diagnostic.level,
+                        diagnostic.code.as_deref(),
+                        diagnostic.message.as_str()
+                    ))
+                    .collect::<Vec<_>>(),
+                vec![
+                    (
+                        Level::Error,
+                        Some(codes::EMPTY_SPECIFIER),
+                        "next/dynamic loaders can't import an empty specifier"
+                    ),
+                    (
+                        warning,
+                        None,
+                        "next/dynamic can't preload modules imported with a non-static specifier. \
+                         Pass a string literal to `import()` instead."
+                    ),
+                    (
+                        warning,
+                        Some(codes::TOO_MANY_ARGUMENTS),
+                        "next/dynamic only accepts 2 arguments"
+                    ),
+                    (
+                        Level::Note,
+                        None,
+                        "next/dynamic can't resolve the `ssr` option through a spread. Pass `ssr` \
+                         explicitly for it to be taken into account at build time."
+                    ),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn should_return_entries_for_each_call() {
+        let result = transform(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const World = dynamic(() => import('../components/world'), { ssr: false })
+            "#,
+        );
+
+        assert_eq!(
+            result.entries,
+            vec![
+                DynamicEntry {
+                    key: Some("src/some-file.js -> ../components/hello".into()),
+                    specifier: "../components/hello".into(),
+                    layer: DynamicEntryLayer::Client,
+                    mode: DynamicEntryMode::Webpack,
+                    branch: DynamicEntryBranch::WebpackKey,
+                },
+                DynamicEntry {
+                    key: Some("src/some-file.js -> ../components/world".into()),
+                    specifier: "../components/world".into(),
+                    layer: DynamicEntryLayer::Client,
+                    mode: DynamicEntryMode::Webpack,
+                    branch: DynamicEntryBranch::WebpackKey,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_return_the_original_key_of_hashed_keys() {
+        let result = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                hash_module_keys: true,
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const HelloAgain = dynamic(() => import('../components/hello'))
+            "#,
+        )
+        .0;
+
+        let hashed_key = "3c77ce4a9a471be55f27c79693577e9b323db888";
+        assert_eq!(result.entries[0].key.as_deref(), Some(hashed_key));
+        assert_eq!(
+            result.hashed_keys,
+            vec![(
+                hashed_key.to_string(),
+                "src/some-file.js -> ../components/hello".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn should_strip_the_project_root_at_path_boundaries() {
+        let rel_filename = |filename: &str, project_root: &str| {
+            let mut patcher = NextDynamicPatcher::new(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Custom(filename.into()).into(),
+                None,
+                Default::default(),
+                NoopComments,
+            );
+            patcher.project_root = Some(project_root.into());
+            patcher.rel_filename()
+        };
+
+        assert_eq!(
+            rel_filename("vfs:/some-project/src/some-file.js", "vfs:/some-project"),
+            "src/some-file.js"
+        );
+        assert_eq!(
+            rel_filename("vfs:/some-project/src/some-file.js", "vfs:/some-project/"),
+            "src/some-file.js"
+        );
+        assert_eq!(
+            rel_filename(
+                "vfs:/some-project-admin/src/some-file.js",
+                "vfs:/some-project"
+            ),
+            "vfs:/some-project-admin/src/some-file.js"
+        );
+    }
+
+    #[test]
+    fn should_collect_hashed_keys_of_the_pass() {
+        let buffer = Arc::new(HashedKeysBuffer::default());
+        let mut program = Program::Module(GLOBALS.set(&Default::default(), || {
+            parse(
+                r#"
+                import dynamic from 'next/dynamic'
+
+                const Hello = dynamic(() => import('../components/hello'))
+                const HelloAgain = dynamic(() => import('../components/hello'))
+                "#,
+            )
+        }));
+        GLOBALS.set(&Default::default(), || {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                Config {
+                    hash_module_keys: true,
+                    hashed_keys_buffer: Some(buffer.clone()),
+                    ..Default::default()
+                },
+                NoopComments,
+            )
+            .process(&mut program)
+        });
+
+        assert_eq!(
+            *buffer.lock().unwrap(),
+            vec![(
+                "3c77ce4a9a471be55f27c79693577e9b323db888".to_string(),
+                "src/some-file.js -> ../components/hello".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn should_report_entries_to_the_sink() {
+        let lines = Arc::new(Mutex::new(vec![]));
+        let sink_lines = lines.clone();
+
+        transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                entry_report_sink: Some(Arc::new(move |line: &str| {
+                    sink_lines.lock().unwrap().push(line.to_string())
+                })),
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const World = dynamic(() => import('../components/world'))
+            "#,
+        );
+
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                r#"{"filename":"/some-project/src/some-file.js","key":"src/some-file.js -> ../components/hello","specifier":"../components/hello","mode":"webpack","layer":"client"}"#,
+                r#"{"filename":"/some-project/src/some-file.js","key":"src/some-file.js -> ../components/world","specifier":"../components/world","mode":"webpack","layer":"client"}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn should_not_return_keys_for_prod_client_entries() {
+        let result = transform(
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            "#,
+        );
+
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].key, None);
+        assert_eq!(result.entries[0].specifier, "../components/hello");
+        assert_eq!(
+            result.entries[0].branch,
+            DynamicEntryBranch::WebpackResolveWeak
+        );
+    }
+
+    #[test]
+    fn should_note_look_alike_imports() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                report_look_alike_imports: true,
+                ..Default::default()
+            },
+            r#"
+            import dynamic from '@loadable/component'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Note,
+                "`dynamic` is imported from '@loadable/component'. Did you mean to import it from \
+                 'next/dynamic'?"
+                    .to_string()
+            )]
+        );
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn should_note_dynamic_passed_as_a_value() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                report_dynamic_as_value: true,
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = wrap(dynamic)(() => import('../components/hello'))
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Note,
+                "`next/dynamic` is passed as a value. The modules loaded by the calls of the \
+                 function receiving it aren't added to the React Loadable Manifest."
+                    .to_string()
+            )]
+        );
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn should_not_note_look_alike_imports_by_default() {
+        let (_, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Default::default(),
+            r#"
+            import dynamic from '@loadable/component'
+            import loadable from 'react-loadable'
+            "#,
+        );
+
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn should_warn_once_without_project_dir() {
+        let diagnostics = CollectedDiagnostics::default();
+        let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+
+        GLOBALS.set(&Default::default(), || {
+            HANDLER.set(&handler, || {
+                next_dynamic_with_entries(
+                    true,
+                    false,
+                    false,
+                    false,
+                    NextDynamicMode::Webpack,
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                    None,
+                    Config {
+                        warn_missing_project_dir: true,
+                        ..Default::default()
+                    },
+                    NoopComments,
+                    parse(
+                        r#"
+                        import dynamic from 'next/dynamic'
+
+                        const Hello = dynamic(() => import('../components/hello'))
+                        const World = dynamic(() => import('../components/world'))
+                        "#,
+                    ),
+                )
+            })
+        });
+
+        let diagnostics = diagnostics.0.lock().unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, Level::Warning);
+        assert!(diagnostics[0].1.contains("no pages or app directory"));
+    }
+
+    #[test]
+    fn should_not_warn_without_project_dir_in_turbopack_mode() {
+        let diagnostics = CollectedDiagnostics::default();
+        let handler = Handler::with_emitter(true, false, Box::new(diagnostics.clone()));
+
+        GLOBALS.set(&Default::default(), || {
+            HANDLER.set(&handler, || {
+                next_dynamic_with_entries(
+                    true,
+                    false,
+                    false,
+                    false,
+                    NextDynamicMode::Turbopack {
+                        dynamic_transition_name: "next-dynamic".into(),
+                        import_attribute_keys: Default::default(),
+                        module_id_export: None,
+                        transition_import_phase: Default::default(),
+                    },
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                    None,
+                    Config {
+                        warn_missing_project_dir: true,
+                        ..Default::default()
+                    },
+                    NoopComments,
+                    parse(
+                        r#"
+                        import dynamic from 'next/dynamic'
+
+                        const Hello = dynamic(() => import('../components/hello'))
+                        "#,
+                    ),
+                )
+            })
+        });
+
+        assert!(diagnostics.0.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn should_rewrite_specifiers() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            "#;
+        let config = Config {
+            specifier_rewriter: Some(Arc::new(|specifier: &str| {
+                Some(format!("{specifier}?canonical"))
+            })),
+            ..Default::default()
+        };
+
+        let (result, _) =
+            transform_with_config(true, false, NextDynamicMode::Webpack, config.clone(), src);
+        assert_eq!(
+            result.entries[0].key.as_deref(),
+            Some("src/some-file.js -> ../components/hello?canonical")
+        );
+
+        let (result, _) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_name: "next-dynamic".into(),
+                import_attribute_keys: Default::default(),
+                module_id_export: None,
+                transition_import_phase: Default::default(),
+            },
+            config,
+            src,
+        );
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = &result.module.body[0] else {
+            panic!("expected the module id import to be added");
+        };
+        assert_eq!(&*import.src.value, "../components/hello?canonical");
+    }
+
+    #[test]
+    fn should_prefix_turbopack_module_id_idents() {
+        let (result, _) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_name: "next-dynamic".into(),
+                import_attribute_keys: Default::default(),
+                module_id_export: None,
+                transition_import_phase: Default::default(),
+            },
+            Config {
+                private_ident_prefix: Some("__next_dyn_id_".into()),
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            "#,
+        );
+
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = &result.module.body[0] else {
+            panic!("expected the module id import to be added");
+        };
+        let [ImportSpecifier::Named(ImportNamedSpecifier { local, .. })] = &*import.specifiers
+        else {
+            panic!("expected a single named import");
+        };
+        assert_eq!(&*local.sym, "__next_dyn_id_src_some_file_js");
+        assert_eq!(
+            turbopack_id_ident_name(Some("__next_dyn_id_"), None),
+            "__next_dyn_id"
+        );
+    }
+
+    #[test]
+    fn should_add_turbopack_imports_in_source_order() {
+        let result = transform(
+            true,
+            false,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_name: "next-dynamic".into(),
+                import_attribute_keys: Default::default(),
+                module_id_export: None,
+                transition_import_phase: Default::default(),
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const World = dynamic(() => import('../components/world'))
+            const Hello = dynamic(() => import('../components/hello'))
+            const Other = dynamic(() => import('../components/other'))
+            "#,
+        );
+
+        let specifiers = result
+            .module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import))
+                    if &*import.src.value != "next/dynamic" =>
+                {
+                    Some(&*import.src.value)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            specifiers,
+            vec![
+                "../components/world",
+                "../components/hello",
+                "../components/other"
+            ]
+        );
+    }
+
+    #[test]
+    fn should_emit_transition_imports_in_the_configured_phase() {
+        let result = transform(
+            false,
+            true,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_name: "next-dynamic".into(),
+                import_attribute_keys: Default::default(),
+                module_id_export: None,
+                transition_import_phase: ImportPhase::Defer,
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            "#,
+        );
+
+        let phases = result
+            .module
+            .body
+            .iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => {
+                    Some((&*import.src.value, import.phase))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            phases,
+            vec![
+                ("../components/hello", ImportPhase::Defer),
+                ("next/dynamic", ImportPhase::Evaluation)
+            ]
+        );
+    }
+
+    #[test]
+    fn should_restore_the_original_calls() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const World = dynamic(() => import('../components/world'), { ssr: false })
+            "#;
+
+        for mode in [
+            NextDynamicMode::Webpack,
+            NextDynamicMode::Turbopack {
+                dynamic_transition_name: "next-dynamic".into(),
+                import_attribute_keys: Default::default(),
+                module_id_export: None,
+                transition_import_phase: Default::default(),
+            },
+        ] {
+            let result = transform(true, false, mode.clone(), src);
+            let mut stripped = Program::Module(result.module);
+            GLOBALS.set(&Default::default(), || {
+                strip_loadable_generated(mode.clone(), Default::default()).process(&mut stripped)
+            });
+
+            // The options objects are recreated by the transform.
+            let original = Program::Module(GLOBALS.set(&Default::default(), || parse(src)));
+            assert!(stripped.eq_ignore_span(&original));
+        }
+    }
+
+    #[test]
+    fn should_restore_guarded_and_namespaced_calls() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const World = dynamic(() => import('../components/world'), { ssr: false })
+            "#;
+
+        for config in [
+            Config {
+                loadable_generated_guard: Some("__NEXT_LOADABLE__".into()),
+                ..Default::default()
+            },
+            Config {
+                loadable_generated_namespace: Some("__next".into()),
+                ..Default::default()
+            },
+        ] {
+            let (result, _) =
+                transform_with_config(true, false, NextDynamicMode::Webpack, config.clone(), src);
+            let mut stripped = Program::Module(result.module);
+            GLOBALS.set(&Default::default(), || {
+                strip_loadable_generated(NextDynamicMode::Webpack, config).process(&mut stripped)
+            });
+
+            let original = Program::Module(GLOBALS.set(&Default::default(), || parse(src)));
+            assert!(stripped.eq_ignore_span(&original));
+        }
+    }
+
+    #[test]
+    fn should_restore_calls_with_extra_arguments_and_preload_exports() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            export const __preload_other = () => import('../components/other')
+
+            const Hello = dynamic(() => import('../components/hello'))
+            const World = dynamic(() => import('../components/world'), {}, extra)
+            const Other = dynamic(() => import('../components/other'), { ssr: true }, extra)
+            "#;
+        let config = Config {
+            preload_exports: true,
+            skip_argument_count_validation: true,
+            ..Default::default()
+        };
+
+        let (result, _) =
+            transform_with_config(true, false, NextDynamicMode::Webpack, config.clone(), src);
+        let mut stripped = Program::Module(result.module);
+        GLOBALS.set(&Default::default(), || {
+            strip_loadable_generated(NextDynamicMode::Webpack, config).process(&mut stripped)
+        });
+
+        let original = Program::Module(GLOBALS.set(&Default::default(), || parse(src)));
+        assert!(stripped.eq_ignore_span(&original));
+    }
+
+    #[test]
+    fn should_note_spread_options() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Default::default(),
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'), { ...options })
+            const World = dynamic(() => import('../components/world'), { ...options, ssr: true })
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Note,
+                "next/dynamic can't resolve the `ssr` option through a spread. Pass `ssr` \
+                 explicitly for it to be taken into account at build time."
+                    .to_string()
+            )]
+        );
+        assert_eq!(result.entries.len(), 2);
+    }
+
+    #[test]
+    fn should_warn_for_non_static_specifiers() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import(`../components/${name}`))
+            "#;
+
+        let (_, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Default::default(),
+            src,
+        );
+        assert!(diagnostics.is_empty());
+
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                report_non_static_specifiers: true,
+                ..Default::default()
+            },
+            src,
+        );
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Warning,
+                "next/dynamic can't preload modules imported with a non-static specifier. Pass a \
+                 string literal to `import()` instead."
+                    .to_string()
+            )]
+        );
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn should_warn_for_complex_loader_helpers() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                follow_loader_helpers: true,
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const load = async () => (await import('../components/hello')).default
+            const Hello = dynamic(() => load())
+            "#,
+        );
+
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Warning,
+                "next/dynamic can't find the module imported by `load`. Helpers are only followed \
+                 when they return `import()` directly."
+                    .to_string()
+            )]
+        );
+        assert!(result.entries.is_empty());
+    }
+
+    #[test]
+    fn should_share_additional_specifiers_across_modules() {
+        let mut additional_specifiers = FxHashSet::default();
+        additional_specifiers.insert("@acme/ui/dynamic".into());
+        let config = Config {
+            additional_specifiers: Arc::new(additional_specifiers),
+            ..Default::default()
+        };
+        // The config of each module, as cloned by the build for its passes.
+        let first_config = config.clone();
+        let second_config = config.clone();
+        assert!(Arc::ptr_eq(
+            &first_config.additional_specifiers,
+            &second_config.additional_specifiers
+        ));
+        assert_eq!(Arc::strong_count(&config.additional_specifiers), 3);
+
+        let (first, _) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            first_config,
+            r#"
+            import dynamic from '@acme/ui/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'))
+            "#,
+        );
+        let (second, _) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            second_config,
+            r#"
+            import dynamic from '@acme/ui/dynamic'
+
+            const World = dynamic(() => import('../components/world'))
+            "#,
+        );
+
+        assert_eq!(
+            first
+                .entries
+                .iter()
+                .map(|entry| entry.specifier.as_str())
+                .collect::<Vec<_>>(),
+            vec!["../components/hello"]
+        );
+        assert_eq!(
+            second
+                .entries
+                .iter()
+                .map(|entry| entry.specifier.as_str())
+                .collect::<Vec<_>>(),
+            vec!["../components/world"]
+        );
+        // The transforms don't keep a copy of the set.
+        assert_eq!(Arc::strong_count(&config.additional_specifiers), 1);
+    }
+
+    #[test]
+    fn should_not_modify_the_module_in_dry_run() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => import('../components/hello'), { ssr: false })
+            "#;
+
+        let (result, _) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                dry_run: true,
+                ..Default::default()
+            },
+            src,
+        );
+
+        assert_eq!(
+            result.module,
+            GLOBALS.set(&Default::default(), || parse(src))
+        );
+        assert_eq!(
+            result.entries,
+            vec![DynamicEntry {
+                key: Some("src/some-file.js -> ../components/hello".into()),
+                specifier: "../components/hello".into(),
+                layer: DynamicEntryLayer::Client,
+                mode: DynamicEntryMode::Webpack,
+                branch: DynamicEntryBranch::WebpackKey,
+            }]
+        );
+    }
+
+    #[test]
+    fn should_note_ssr_option_usage() {
+        let src = r#"
+            import dynamic from 'next/dynamic'
+
+            const SsrTrue = dynamic(() => import('../components/hello'), { ssr: true })
+            const SsrFalse = dynamic(() => import('../components/hello'), { ssr: false })
+            "#;
+
+        let (_, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                report_ssr_option_usage: true,
+                ..Default::default()
+            },
+            src,
+        );
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    Level::Note,
+                    "`ssr: true` is the default for next/dynamic and can be removed.\nRead more: \
+                     https://nextjs.org/docs/app/building-your-application/optimizing/lazy-loading"
+                        .to_string()
+                ),
+                (
+                    Level::Note,
+                    "`ssr: false` disables server-side rendering of this component, make sure \
+                     this is still needed.\nRead more: \
+                     https://nextjs.org/docs/app/building-your-application/optimizing/lazy-loading#skipping-ssr"
+                        .to_string()
+                ),
+            ]
+        );
+
+        let (_, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Default::default(),
+            src,
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn should_apply_custom_resolve_weak_policy() {
+        let (result, _) = transform_with_config(
+            false,
+            false,
+            NextDynamicMode::Webpack,
+            Config {
+                resolve_weak_policy: Some(Arc::new(|ctx: &ResolveWeakContext| {
+                    ctx.has_ssr_false && !ctx.is_server_compiler
+                })),
+                ..Default::default()
+            },
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const SsrFalse = dynamic(() => import('../components/hello'), { ssr: false })
+            const SsrTrue = dynamic(() => import('../components/hello'), { ssr: true })
+            "#,
+        );
+
+        let loaders: Vec<_> = result.module.body[1..]
+            .iter()
+            .map(|item| {
+                let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var))) = item else {
+                    panic!("expected a variable declaration");
+                };
+                let Some(Expr::Call(call)) = var.decls[0].init.as_deref() else {
+                    panic!("expected a call");
+                };
+                call.args[0].expr.clone()
+            })
+            .collect();
+
+        assert!(matches!(
+            &*loaders[0],
+            Expr::Arrow(ArrowExpr { is_async: true, body, .. }) if body.is_block_stmt()
+        ));
+        assert!(matches!(
+            &*loaders[1],
+            Expr::Arrow(ArrowExpr {
+                is_async: false,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn module_id_options_should_contain_a_single_module() {
+        let props = module_id_options(vec![Expr::Lit("../components/hello".into())], false, None);
+
+        let [PropOrSpread::Prop(prop)] = &*props else {
+            panic!("expected a single property");
+        };
+        let Prop::KeyValue(KeyValueProp { key, value }) = &**prop else {
+            panic!("expected a key-value property");
+        };
+        assert!(matches!(key, PropName::Ident(IdentName { sym, .. }) if &**sym == "modules"));
+
+        let Expr::Array(ArrayLit { elems, .. }) = &**value else {
+            panic!("expected an array");
+        };
+        let [Some(ExprOrSpread { spread: None, expr })] = &**elems else {
+            panic!("expected a single non-sparse element");
+        };
+        assert!(matches!(
+            &**expr,
+            Expr::Lit(Lit::Str(Str { value, .. })) if &**value == "../components/hello"
+        ));
+    }
+
+    #[test]
+    fn should_report_next_dynamic_re_exports() {
+        let result = transform(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            "export { default } from 'next/dynamic'",
+        );
+        assert!(result.reexports_dynamic);
+
+        let result = transform(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            "export { default } from 'next/link'",
+        );
+        assert!(!result.reexports_dynamic);
+    }
+
+    #[test]
+    fn should_ignore_calls_to_other_callees() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Default::default(),
+            r#"
+            import dynamic from 'next/dynamic'
+
+            class Loadable extends Base {
+                constructor() {
+                    super(() => import('../components/hello'))
+                }
+            }
+
+            const Member = dynamic.default(() => import('../components/hello'))
+            const Nested = components.dynamic(() => import('../components/hello'))
+            const Tagged = dynamic`../components/hello`
+            "#,
+        );
+
+        assert!(result.entries.is_empty());
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn should_collect_every_imported_module() {
+        let (result, diagnostics) = transform_with_config(
+            true,
+            false,
+            NextDynamicMode::Webpack,
+            Default::default(),
+            r#"
+            import dynamic from 'next/dynamic'
+
+            const Hello = dynamic(() => {
+                if (isMobile) return import('../components/mobile')
+                return import('../components/desktop')
+            })
+            "#,
+        );
+
+        assert_eq!(
+            result
+                .entries
+                .iter()
+                .map(|entry| entry.specifier.as_str())
+                .collect::<Vec<_>>(),
+            vec!["../components/mobile", "../components/desktop"]
+        );
+        assert_eq!(
+            diagnostics,
+            vec![(
+                Level::Warning,
+                "next/dynamic loaders should import a single module. All the modules imported by \
+                 this loader are added to `loadableGenerated`."
+                    .to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn should_report_error_codes() {
+        // The codes are reported separately, so that the messages stay the same for
+        // consumers matching on them.
+        let errors = |src| {
+            let (_, diagnostics) = transform_with_diagnostics(
+                true,
+                false,
+                NextDynamicMode::Webpack,
+                Default::default(),
+                src,
+            );
+            diagnostics
+                .into_iter()
+                .map(|(_, message, code)| (message, code))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            errors(
+                r#"
+                import dynamic from 'next/dynamic'
+
+                const Hello = dynamic()
+                "#
+            ),
+            vec![(
+                "next/dynamic requires at least one argument".to_string(),
+                Some(DiagnosticId::Error(codes::NO_ARGUMENTS.into()))
+            )]
+        );
+        assert_eq!(
+            errors(
+                r#"
+                import dynamic from 'next/dynamic'
+
+                const Hello = dynamic(() => import('../components/hello'), options)
+                "#
+            ),
+            vec![(
+                "next/dynamic options must be an object literal.\nRead more: \
+                 https://nextjs.org/docs/messages/invalid-dynamic-options-type"
+                    .to_string(),
+                Some(DiagnosticId::Error(codes::INVALID_OPTIONS.into()))
+            )]
+        );
+        assert_eq!(
+            errors(
+                r#"
+                import dynamic from 'next/dynamic'
+
+                const Hello = dynamic(() => import('../components/hello'), {}, {})
+                "#
+            ),
+            vec![(
+                "next/dynamic only accepts 2 arguments".to_string(),
+                Some(DiagnosticId::Error(codes::TOO_MANY_ARGUMENTS.into()))
+            )]
+        );
+    }
 }
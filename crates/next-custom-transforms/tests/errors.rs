@@ -2,7 +2,10 @@ use std::{iter::FromIterator, path::PathBuf};
 
 use next_custom_transforms::transforms::{
     disallow_re_export_all_in_page::disallow_re_export_all_in_page,
-    dynamic::{next_dynamic, NextDynamicMode},
+    dynamic::{
+        next_dynamic, ArgumentCountSeverity, Config as NextDynamicConfig, NextDynamicMode,
+        SsrDefault,
+    },
     fonts::{next_font_loaders, Config as FontLoaderConfig},
     next_ssg::next_ssg,
     react_server_components::server_components,
@@ -52,7 +55,197 @@ fn next_dynamic_errors(input: PathBuf) {
     let output = input.parent().unwrap().join("output.js");
     test_fixture(
         syntax(),
-        &|_tr| {
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                Default::default(),
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-treat-warnings-as-errors/**/input.js")]
+fn next_dynamic_treat_warnings_as_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    report_non_static_specifiers: true,
+                    treat_warnings_as_errors: true,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-ssr-default/**/input.js")]
+fn next_dynamic_ssr_default_errors(input: PathBuf) {
+    let output_enabled = input.parent().unwrap().join("output-enabled.js");
+    let output_disabled = input.parent().unwrap().join("output-disabled.js");
+    for (output, ssr_default) in [
+        (output_enabled, SsrDefault::Enabled),
+        (output_disabled, SsrDefault::Disabled),
+    ] {
+        test_fixture(
+            syntax(),
+            &|tr| {
+                next_dynamic(
+                    false,
+                    true,
+                    false,
+                    true,
+                    NextDynamicMode::Webpack,
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                    Some("/some-project/src".into()),
+                    NextDynamicConfig {
+                        ssr_default,
+                        ..Default::default()
+                    },
+                    tr.comments.as_ref().clone(),
+                )
+            },
+            &input,
+            &output,
+            FixtureTestConfig {
+                allow_error: true,
+                module: Some(true),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+#[fixture("tests/errors/next-dynamic-loader-helpers/**/input.js")]
+fn next_dynamic_loader_helpers_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    follow_loader_helpers: true,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-look-alike-imports/**/input.js")]
+fn next_dynamic_look_alike_imports_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    report_look_alike_imports: true,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-max-specifier-length/**/input.js")]
+fn next_dynamic_max_specifier_length_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    max_specifier_length: Some(32),
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-argument-count-warning/**/input.js")]
+fn next_dynamic_argument_count_warning_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
             next_dynamic(
                 true,
                 false,
@@ -61,6 +254,107 @@ fn next_dynamic_errors(input: PathBuf) {
                 NextDynamicMode::Webpack,
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
                 Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    argument_count_severity: ArgumentCountSeverity::Warning,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-as-value/**/input.js")]
+fn next_dynamic_as_value_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    report_dynamic_as_value: true,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/errors/next-dynamic-is-test/**/input.js")]
+fn next_dynamic_is_test_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                false,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                Some("/some-project/src".into()),
+                NextDynamicConfig {
+                    is_test: true,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
+            )
+        },
+        &input,
+        &output,
+        FixtureTestConfig {
+            allow_error: true,
+            module: Some(true),
+            ..Default::default()
+        },
+    );
+}
+
+/// The crossings are reported as warnings, see the unit tests of the transform.
+/// Here they are reported as errors to assert their spans.
+#[fixture("tests/errors/next-dynamic-router-boundary/**/input.js")]
+fn next_dynamic_router_boundary_treat_warnings_as_errors(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|tr| {
+            next_dynamic(
+                true,
+                false,
+                false,
+                false,
+                NextDynamicMode::Webpack,
+                FileName::Real(PathBuf::from("/some-project/src/app/page.js")).into(),
+                Some("/some-project/src/app".into()),
+                NextDynamicConfig {
+                    report_router_boundary_crossings: true,
+                    treat_warnings_as_errors: true,
+                    ..Default::default()
+                },
+                tr.comments.as_ref().clone(),
             )
         },
         &input,
@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+
+const NoArguments = dynamic()
+const TooManyArguments = dynamic(() => import('../components/hello'), {}, {})
@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic';
+const NoArguments = dynamic();
+const TooManyArguments = dynamic(()=>import('../components/hello'), {}, {});
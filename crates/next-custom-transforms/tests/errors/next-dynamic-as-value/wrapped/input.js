@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic'
+
+const Hello = wrap(dynamic)(() => import('../components/hello'))
@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+
+const Hello = dynamic(() => import('../components/hello'), { ssr: false })
+const Invalid = dynamic(() => import('../components/hello'), {}, {})
@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic'
+
+const loadHello = () => {
+  console.log('loading')
+  return import('../components/hello')
+}
+
+const DynamicComponent = dynamic(() => loadHello())
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic';
+const loadHello = ()=>{
+    console.log('loading');
+    return import('../components/hello');
+};
+const DynamicComponent = dynamic(()=>loadHello());
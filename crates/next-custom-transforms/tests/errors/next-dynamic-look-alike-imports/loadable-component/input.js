@@ -0,0 +1,3 @@
+import dynamic from '@loadable/component'
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
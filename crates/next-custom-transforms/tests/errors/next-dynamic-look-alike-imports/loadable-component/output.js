@@ -0,0 +1,2 @@
+import dynamic from '@loadable/component';
+const DynamicComponent = dynamic(()=>import('../components/hello'));
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const Short = dynamic(() => import('../components/hello'))
+const Long = dynamic(
+  () => import('../generated/a-very-long-module-name-produced-by-a-code-generator')
+)
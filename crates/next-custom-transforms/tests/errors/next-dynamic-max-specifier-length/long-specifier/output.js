@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic';
+const Short = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+const Long = dynamic(()=>import('../generated/a-very-long-module-name-produced-by-a-code-generator'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../generated/a-very-lon~ed59fbf0"
+        ]
+    }
+});
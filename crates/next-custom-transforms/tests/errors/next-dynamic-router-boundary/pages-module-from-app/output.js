@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../pages/hello'), {
+    loadableGenerated: {
+        modules: [
+            "app/page.js -> " + "../pages/hello"
+        ]
+    }
+});
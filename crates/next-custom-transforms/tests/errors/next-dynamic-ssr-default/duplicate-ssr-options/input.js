@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic'
+
+export const SsrFalseLast = dynamic(() => import('../components/hello'), {
+  ssr: true,
+  ssr: false,
+})
+
+export const SsrTrueLast = dynamic(() => import('../components/world'), {
+  ssr: false,
+  ssr: true,
+})
+
+export const SsrGetterLast = dynamic(() => import('../components/world'), {
+  ssr: false,
+  get ssr() {
+    return true
+  },
+})
@@ -0,0 +1,32 @@
+import dynamic from 'next/dynamic';
+export const SsrFalseLast = dynamic(async ()=>{
+    typeof require.resolveWeak !== "undefined" && require.resolveWeak("../components/hello");
+}, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: true,
+    ssr: false
+});
+export const SsrTrueLast = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    },
+    ssr: false,
+    ssr: true
+});
+export const SsrGetterLast = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    },
+    ssr: false,
+    get ssr () {
+        return true;
+    }
+});
@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic'
+
+export const Data = dynamic(() => import('./data.json'), { ssr: false })
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+export const Data = dynamic(()=>import('./data.json'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "./data.json"
+        ]
+    },
+    ssr: false
+});
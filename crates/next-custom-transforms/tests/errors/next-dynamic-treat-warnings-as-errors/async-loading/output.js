@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    loading: async ()=><p>Loading...</p>
+});
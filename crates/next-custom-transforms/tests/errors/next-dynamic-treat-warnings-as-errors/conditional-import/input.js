@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+
+const isEnabled = process.env.NEXT_PUBLIC_HELLO === 'on'
+const DynamicComponent = dynamic(() => isEnabled && import('../components/hello'))
@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+import loadHello from '../components/load-hello'
+
+const DynamicComponent = dynamic(loadHello)
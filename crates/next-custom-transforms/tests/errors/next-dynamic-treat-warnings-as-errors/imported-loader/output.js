@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic';
+import loadHello from '../components/load-hello';
+const DynamicComponent = dynamic(loadHello);
@@ -0,0 +1,2 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import(new URL('../components/hello', import.meta.url)));
@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic';
+const name = 'hello';
+const DynamicComponent = dynamic(()=>import(`../components/${name}`));
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(() => {
+  if (isMobile) return import('../components/mobile')
+  return import('../components/desktop')
+})
@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>{
+    if (isMobile) return import('../components/mobile');
+    return import('../components/desktop');
+}, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/mobile",
+            "src/some-file.js -> " + "../components/desktop"
+        ]
+    }
+});
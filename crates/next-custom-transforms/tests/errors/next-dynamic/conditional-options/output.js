@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), cond ? {
+    ssr: false
+} : {});
@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+
+const WithParameters = dynamic((name) => import('../components/' + name))
+const WithoutParameters = dynamic(() => import('../components/hello'))
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const WithParameters = dynamic((name)=>import('../components/' + name));
+const WithoutParameters = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
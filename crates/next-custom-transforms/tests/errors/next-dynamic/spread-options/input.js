@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const options = { loading: () => null }
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  ...options,
+})
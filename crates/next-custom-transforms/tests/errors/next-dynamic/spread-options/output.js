@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+const options = {
+    loading: ()=>null
+};
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ...options
+});
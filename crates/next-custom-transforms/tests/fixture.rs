@@ -2,13 +2,18 @@ use std::{
     env::current_dir,
     iter::FromIterator,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use next_custom_transforms::transforms::{
     amp_attributes::amp_attributes,
     cjs_optimizer::cjs_optimizer,
     debug_fn_name::debug_fn_name,
-    dynamic::{next_dynamic, NextDynamicMode},
+    dynamic::{
+        next_dynamic, next_dynamic_with_project_root, strip_loadable_generated,
+        Config as NextDynamicConfig, ImportAttributeKeys, NextDynamicMode, SpecifierQuery,
+        SsrDefault,
+    },
     fonts::{next_font_loaders, Config as FontLoaderConfig},
     named_import_transform::named_import_transform,
     next_ssg::next_ssg,
@@ -48,6 +53,105 @@ fn syntax() -> Syntax {
     })
 }
 
+/// The Turbopack mode of `next_dynamic` emits imports with a `with` clause.
+fn turbopack_syntax() -> Syntax {
+    Syntax::Es(EsSyntax {
+        jsx: true,
+        import_attributes: true,
+        ..Default::default()
+    })
+}
+
+/// The compilation a `next_dynamic` fixture runs in. Unless overridden, the
+/// module is `/some-project/src/some-file.js` in the `/some-project/src`
+/// pages directory.
+struct Build {
+    is_development: bool,
+    is_server_compiler: bool,
+    is_react_server_layer: bool,
+    prefer_esm: bool,
+    filename: FileName,
+    pages_or_app_dir: Option<PathBuf>,
+}
+
+impl Build {
+    fn new(is_development: bool, is_server_compiler: bool) -> Self {
+        Build {
+            is_development,
+            is_server_compiler,
+            is_react_server_layer: false,
+            prefer_esm: false,
+            filename: FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
+            pages_or_app_dir: Some("/some-project/src".into()),
+        }
+    }
+
+    fn dev_client() -> Self {
+        Build::new(true, false)
+    }
+
+    fn prod_client() -> Self {
+        Build::new(false, false)
+    }
+
+    fn prod_server() -> Self {
+        Build::new(false, true)
+    }
+}
+
+/// The Turbopack mode, with the transition and attribute keys of Next.js.
+fn turbopack_mode() -> NextDynamicMode {
+    NextDynamicMode::Turbopack {
+        dynamic_transition_name: "next-dynamic".into(),
+        import_attribute_keys: Default::default(),
+        module_id_export: None,
+        transition_import_phase: Default::default(),
+    }
+}
+
+/// Asserts that `next_dynamic` transforms `input` into `output`.
+fn run_next_dynamic_fixture(
+    input: &Path,
+    output: &Path,
+    build: Build,
+    mode: NextDynamicMode,
+    config: NextDynamicConfig,
+) {
+    let syntax = match (&mode, input.extension().and_then(|ext| ext.to_str())) {
+        (_, Some("ts")) => Syntax::Typescript(Default::default()),
+        (NextDynamicMode::Turbopack { .. }, _) => turbopack_syntax(),
+        (NextDynamicMode::Webpack, _) => syntax(),
+    };
+    let Build {
+        is_development,
+        is_server_compiler,
+        is_react_server_layer,
+        prefer_esm,
+        filename,
+        pages_or_app_dir,
+    } = build;
+    let filename = Arc::new(filename);
+    test_fixture(
+        syntax,
+        &|tr| {
+            next_dynamic(
+                is_development,
+                is_server_compiler,
+                is_react_server_layer,
+                prefer_esm,
+                mode.clone(),
+                filename.clone(),
+                pages_or_app_dir.clone(),
+                config.clone(),
+                tr.comments.as_ref().clone(),
+            )
+        },
+        input,
+        output,
+        Default::default(),
+    );
+}
+
 #[fixture("tests/fixture/amp/**/input.js")]
 fn amp_attributes_fixture(input: PathBuf) {
     let output = input.parent().unwrap().join("output.js");
@@ -65,55 +169,25 @@ fn next_dynamic_fixture(input: PathBuf) {
     let output_dev = input.parent().unwrap().join("output-dev.js");
     let output_prod = input.parent().unwrap().join("output-prod.js");
     let output_server = input.parent().unwrap().join("output-server.js");
-    test_fixture(
-        syntax(),
-        &|_tr| {
-            next_dynamic(
-                true,
-                false,
-                false,
-                false,
-                NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
-            )
-        },
+    run_next_dynamic_fixture(
         &input,
         &output_dev,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
         Default::default(),
     );
-    test_fixture(
-        syntax(),
-        &|_tr| {
-            next_dynamic(
-                false,
-                false,
-                false,
-                false,
-                NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
-            )
-        },
+    run_next_dynamic_fixture(
         &input,
         &output_prod,
+        Build::prod_client(),
+        NextDynamicMode::Webpack,
         Default::default(),
     );
-    test_fixture(
-        syntax(),
-        &|_tr| {
-            next_dynamic(
-                false,
-                true,
-                false,
-                false,
-                NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
-            )
-        },
+    run_next_dynamic_fixture(
         &input,
         &output_server,
+        Build::prod_server(),
+        NextDynamicMode::Webpack,
         Default::default(),
     );
 }
@@ -127,76 +201,843 @@ fn app_dir_next_dynamic_fixture(input: PathBuf) {
         .parent()
         .unwrap()
         .join("output-server-client-layer.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_dev,
+        Build {
+            is_react_server_layer: true,
+            ..Build::dev_client()
+        },
+        NextDynamicMode::Webpack,
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_prod,
+        Build {
+            is_react_server_layer: true,
+            ..Build::prod_client()
+        },
+        NextDynamicMode::Webpack,
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_server,
+        Build {
+            is_react_server_layer: true,
+            ..Build::prod_server()
+        },
+        NextDynamicMode::Webpack,
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_server_client_layer,
+        Build::prod_server(),
+        NextDynamicMode::Webpack,
+        Default::default(),
+    );
+}
+
+/// Runs `next_dynamic` on the output of another fixture and asserts that it's
+/// left unchanged, i.e. that transforming a module twice is a no-op.
+fn run_next_dynamic_idempotency_test(output: &Path, build: Build) {
+    run_next_dynamic_fixture(
+        output,
+        output,
+        build,
+        NextDynamicMode::Webpack,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic/**/output-*.js")]
+fn next_dynamic_idempotency_fixture(output: PathBuf) {
+    let build = match output.file_name().unwrap().to_str().unwrap() {
+        "output-dev.js" => Build::dev_client(),
+        "output-prod.js" => Build::prod_client(),
+        "output-server.js" => Build::prod_server(),
+        name => panic!("unexpected next-dynamic fixture output: {}", name),
+    };
+    run_next_dynamic_idempotency_test(&output, build);
+}
+
+#[fixture("tests/fixture/next-dynamic-app-dir/**/output-*.js")]
+fn app_dir_next_dynamic_idempotency_fixture(output: PathBuf) {
+    let (build, is_react_server_layer) = match output.file_name().unwrap().to_str().unwrap() {
+        "output-dev.js" => (Build::dev_client(), true),
+        "output-prod.js" => (Build::prod_client(), true),
+        "output-server.js" => (Build::prod_server(), true),
+        "output-server-client-layer.js" => (Build::prod_server(), false),
+        name => panic!("unexpected next-dynamic-app-dir fixture output: {}", name),
+    };
+    run_next_dynamic_idempotency_test(
+        &output,
+        Build {
+            is_react_server_layer,
+            ..build
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-ssr-default/**/input.js")]
+fn next_dynamic_ssr_default_fixture(input: PathBuf) {
+    let output_enabled = input.parent().unwrap().join("output-enabled.js");
+    let output_disabled = input.parent().unwrap().join("output-disabled.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_enabled,
+        Build {
+            prefer_esm: true,
+            ..Build::prod_server()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            ssr_default: SsrDefault::Enabled,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_disabled,
+        Build {
+            prefer_esm: true,
+            ..Build::prod_server()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            ssr_default: SsrDefault::Disabled,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-turbopack-project-dir/**/input.js")]
+fn next_dynamic_turbopack_project_dir_fixture(input: PathBuf) {
+    let output_with_dir = input.parent().unwrap().join("output-with-dir.js");
+    let output_without_dir = input.parent().unwrap().join("output-without-dir.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_with_dir,
+        Build::dev_client(),
+        turbopack_mode(),
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_without_dir,
+        Build {
+            pages_or_app_dir: None,
+            ..Build::dev_client()
+        },
+        turbopack_mode(),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-skip-argument-count-validation/**/input.js")]
+fn next_dynamic_skip_argument_count_validation_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            skip_argument_count_validation: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-custom-filename/**/input.js")]
+fn next_dynamic_custom_filename_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build {
+            filename: FileName::Custom("virtual:entry".into()),
+            ..Build::dev_client()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            custom_filename_mapper: Some(Arc::new(|name: &str| {
+                name.strip_prefix("virtual:")
+                    .map(|name| format!("virtual/{name}.js"))
+            })),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-project-root/**/input.js")]
+fn next_dynamic_project_root_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
     test_fixture(
         syntax(),
-        &|_tr| {
-            next_dynamic(
+        &|tr| {
+            next_dynamic_with_project_root(
                 true,
                 false,
-                true,
+                false,
                 false,
                 NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
+                FileName::Custom("vfs:/some-project/src/some-file.js".into()).into(),
+                "vfs:/some-project".into(),
+                Default::default(),
+                tr.comments.as_ref().clone(),
             )
         },
         &input,
-        &output_dev,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-loader-helpers/**/input.js")]
+fn next_dynamic_loader_helpers_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            follow_loader_helpers: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-loadable-generated-namespace/**/input.js")]
+fn next_dynamic_loadable_generated_namespace_fixture(input: PathBuf) {
+    let output_flat = input.parent().unwrap().join("output-flat.js");
+    let output_namespaced = input.parent().unwrap().join("output-namespaced.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_flat,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_namespaced,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            loadable_generated_namespace: Some("__next".into()),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-import-attribute-keys/**/input.js")]
+fn next_dynamic_import_attribute_keys_fixture(input: PathBuf) {
+    let output_dev_client = input.parent().unwrap().join("output-dev-client.js");
+    let output_prod_server = input.parent().unwrap().join("output-prod-server.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_dev_client,
+        Build::dev_client(),
+        NextDynamicMode::Turbopack {
+            dynamic_transition_name: "next-dynamic".into(),
+            import_attribute_keys: ImportAttributeKeys {
+                transition: "bundler-transition".into(),
+                chunking_type: "bundler-chunking-type".into(),
+                chunk_group: "bundler-chunk-group".into(),
+            },
+            module_id_export: None,
+            transition_import_phase: Default::default(),
+        },
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_prod_server,
+        Build::prod_server(),
+        NextDynamicMode::Turbopack {
+            dynamic_transition_name: "next-dynamic".into(),
+            import_attribute_keys: ImportAttributeKeys {
+                transition: "bundler-transition".into(),
+                chunking_type: "bundler-chunking-type".into(),
+                chunk_group: "bundler-chunk-group".into(),
+            },
+            module_id_export: None,
+            transition_import_phase: Default::default(),
+        },
         Default::default(),
     );
+}
+
+#[fixture("tests/fixture/next-dynamic-cjs/**/input.js")]
+fn next_dynamic_cjs_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
     test_fixture(
         syntax(),
-        &|_tr| {
-            next_dynamic(
-                false,
-                false,
-                true,
-                false,
-                NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
+        &|tr| {
+            (
+                resolver(Mark::new(), Mark::new(), false),
+                next_dynamic(
+                    true,
+                    false,
+                    false,
+                    false,
+                    NextDynamicMode::Webpack,
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                    Some("/some-project/src".into()),
+                    Default::default(),
+                    tr.comments.as_ref().clone(),
+                ),
             )
         },
         &input,
-        &output_prod,
+        &output,
         Default::default(),
     );
+}
+
+#[fixture("tests/fixture/next-dynamic-minified/**/input.js")]
+fn next_dynamic_minified_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
     test_fixture(
         syntax(),
-        &|_tr| {
-            next_dynamic(
-                false,
-                true,
-                true,
-                false,
-                NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
+        &|tr| {
+            (
+                resolver(Mark::new(), Mark::new(), false),
+                next_dynamic(
+                    true,
+                    false,
+                    false,
+                    false,
+                    NextDynamicMode::Webpack,
+                    FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
+                    Some("/some-project/src".into()),
+                    Default::default(),
+                    tr.comments.as_ref().clone(),
+                ),
             )
         },
         &input,
-        &output_server,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-module-id-export/**/input.js")]
+fn next_dynamic_module_id_export_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Turbopack {
+            dynamic_transition_name: "next-dynamic".into(),
+            import_attribute_keys: Default::default(),
+            module_id_export: Some("__custom_module_id__".into()),
+            transition_import_phase: Default::default(),
+        },
         Default::default(),
     );
+}
+
+#[fixture("tests/fixture/next-dynamic-preload-exports/**/input.js")]
+fn next_dynamic_preload_exports_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    let output_server = input.parent().unwrap().join("output-server.js");
+    let output_turbopack = input.parent().unwrap().join("output-turbopack.js");
+    let config = NextDynamicConfig {
+        preload_exports: true,
+        ..Default::default()
+    };
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        config.clone(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_server,
+        Build {
+            prefer_esm: true,
+            ..Build::prod_server()
+        },
+        NextDynamicMode::Webpack,
+        config.clone(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_turbopack,
+        Build::dev_client(),
+        turbopack_mode(),
+        config,
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-const-aliases/**/input.js")]
+fn next_dynamic_const_aliases_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            follow_const_aliases: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-lowercase-key-paths/**/input.js")]
+fn next_dynamic_lowercase_key_paths_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build {
+            filename: FileName::Real(PathBuf::from("/some-project/src/Components/SomeFile.js")),
+            ..Build::dev_client()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            lowercase_key_paths: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-specifier-query/**/input.js")]
+fn next_dynamic_specifier_query_fixture(input: PathBuf) {
+    let output_preserved = input.parent().unwrap().join("output-preserved.js");
+    let output_stripped = input.parent().unwrap().join("output-stripped.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_preserved,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            specifier_query: SpecifierQuery::Preserve,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_stripped,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            specifier_query: SpecifierQuery::Strip,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-static-keys/**/input.js")]
+fn next_dynamic_static_keys_fixture(input: PathBuf) {
+    let output_concatenated = input.parent().unwrap().join("output-concatenated.js");
+    let output_static = input.parent().unwrap().join("output-static.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_concatenated,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            static_keys: false,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_static,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            static_keys: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-export-name-keys/**/input.js")]
+fn next_dynamic_export_name_keys_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            export_name_keys: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-loadable-generated-guard/**/input.js")]
+fn next_dynamic_loadable_generated_guard_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            loadable_generated_guard: Some("__NEXT_LOADABLE__".into()),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-legacy-module-key/**/input.js")]
+fn next_dynamic_legacy_module_key_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            legacy_module_key: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-strip-loadable-generated/**/input.js")]
+fn next_dynamic_strip_loadable_generated_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    let mode = NextDynamicMode::Turbopack {
+        dynamic_transition_name: "next-dynamic".into(),
+        import_attribute_keys: ImportAttributeKeys {
+            transition: "bundler-transition".into(),
+            chunking_type: "bundler-chunking-type".into(),
+            chunk_group: "bundler-chunk-group".into(),
+        },
+        module_id_export: Some("__custom_module_id__".into()),
+        transition_import_phase: Default::default(),
+    };
     test_fixture(
-        syntax(),
-        &|_tr| {
-            next_dynamic(
-                false,
-                true,
-                false,
-                false,
-                NextDynamicMode::Webpack,
-                FileName::Real(PathBuf::from("/some-project/src/some-file.js")).into(),
-                Some("/some-project/src".into()),
-            )
+        turbopack_syntax(),
+        &|_tr| strip_loadable_generated(mode.clone(), Default::default()),
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-hashed-keys/**/input.js")]
+fn next_dynamic_hashed_keys_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            hash_module_keys: true,
+            ..Default::default()
         },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-module-hash/**/input.js")]
+fn next_dynamic_module_hash_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
         &input,
-        &output_server_client_layer,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            module_hash: Some("1a2b3c4d5e6f7a8b".into()),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-loadable-generated-ssr/**/input.js")]
+fn next_dynamic_loadable_generated_ssr_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            loadable_generated_ssr: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-base-dirs/**/input.js")]
+fn next_dynamic_base_dirs_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build {
+            filename: FileName::Real(PathBuf::from("/some-project/src/app/(group)/page.js")),
+            pages_or_app_dir: Some("/some-project/src/app".into()),
+            ..Build::dev_client()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            base_dirs: vec![
+                "/some-project/src/app".into(),
+                "/some-project/src/app/(group)".into(),
+            ],
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-normalize-specifiers/**/input.js")]
+fn next_dynamic_normalize_specifiers_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            normalize_specifiers: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-unified-keys/**/input.js")]
+fn next_dynamic_unified_keys_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::prod_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            unified_keys: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-url-keys/**/input.js")]
+fn next_dynamic_url_keys_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            url_keys: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-strip-server-loading/**/input.js")]
+fn next_dynamic_strip_server_loading_fixture(input: PathBuf) {
+    let output_client = input.parent().unwrap().join("output-client.js");
+    let output_server = input.parent().unwrap().join("output-server.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_client,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            strip_server_loading: true,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_server,
+        Build::prod_server(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            strip_server_loading: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-resolve-weak-callee/**/input.js")]
+fn next_dynamic_resolve_weak_callee_fixture(input: PathBuf) {
+    let output_prod = input.parent().unwrap().join("output-prod.js");
+    let output_server = input.parent().unwrap().join("output-server.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_prod,
+        Build::prod_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            resolve_weak_callee: Some("__custom_resolve_weak__".into()),
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_server,
+        Build {
+            prefer_esm: true,
+            ..Build::prod_server()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            resolve_weak_callee: Some("__custom_resolve_weak__".into()),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-lazy-module-ids/**/input.js")]
+fn next_dynamic_lazy_module_ids_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    let output_lazy = input.parent().unwrap().join("output-lazy.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            lazy_module_ids: false,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_lazy,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            lazy_module_ids: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-additional-specifiers/**/input.js")]
+fn next_dynamic_additional_specifiers_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            additional_specifiers: Arc::new(FxHashSet::from_iter(["@acme/ui/dynamic".into()])),
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-typescript/**/input.ts")]
+fn next_dynamic_typescript_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.ts");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
         Default::default(),
     );
 }
 
+#[fixture("tests/fixture/next-dynamic-hybrid-module-ids/**/input.js")]
+fn next_dynamic_hybrid_module_ids_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    let output_legacy_module_key = input.parent().unwrap().join("output-legacy-module-key.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        turbopack_mode(),
+        NextDynamicConfig {
+            hybrid_module_ids: true,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_legacy_module_key,
+        Build::dev_client(),
+        turbopack_mode(),
+        NextDynamicConfig {
+            hybrid_module_ids: true,
+            legacy_module_key: true,
+            ..Default::default()
+        },
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-chunk-group/**/input.js")]
+fn next_dynamic_chunk_group_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        turbopack_mode(),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-turbopack/**/input.js")]
+fn next_dynamic_turbopack_fixture(input: PathBuf) {
+    let output_dev_client = input.parent().unwrap().join("output-dev-client.js");
+    let output_prod_server = input.parent().unwrap().join("output-prod-server.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output_dev_client,
+        Build::dev_client(),
+        turbopack_mode(),
+        Default::default(),
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_prod_server,
+        Build::prod_server(),
+        turbopack_mode(),
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/next-dynamic-prefetch/**/input.js")]
+fn next_dynamic_prefetch_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    let output_server = input.parent().unwrap().join("output-server.js");
+    run_next_dynamic_fixture(
+        &input,
+        &output,
+        Build::dev_client(),
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            prefetch_option: true,
+            ..Default::default()
+        },
+    );
+    run_next_dynamic_fixture(
+        &input,
+        &output_server,
+        Build {
+            prefer_esm: true,
+            ..Build::prod_server()
+        },
+        NextDynamicMode::Webpack,
+        NextDynamicConfig {
+            prefetch_option: true,
+            ..Default::default()
+        },
+    );
+}
+
 #[fixture("tests/fixture/ssg/**/input.js")]
 fn next_ssg_fixture(input: PathBuf) {
     let output = input.parent().unwrap().join("output.js");
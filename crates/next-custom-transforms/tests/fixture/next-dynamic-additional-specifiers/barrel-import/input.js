@@ -0,0 +1,3 @@
+import dynamic from '@acme/ui/dynamic'
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic'
+
+const Widget = dynamic(() => import('../components/widget'), { chunkGroup: 'widgets' })
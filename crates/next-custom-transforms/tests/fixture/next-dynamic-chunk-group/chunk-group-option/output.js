@@ -0,0 +1,13 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../components/widget" with {
+    "chunking-type": "none",
+    "turbopack-chunk-group": "widgets"
+};
+import dynamic from 'next/dynamic';
+const Widget = dynamic(()=>import('../components/widget'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    },
+    chunkGroup: 'widgets'
+});
@@ -0,0 +1,9 @@
+const Inner = (() => {
+  const dynamic = require('next/dynamic')
+
+  return dynamic(() => import('../components/hello'))
+})()
+
+function outer(dynamic) {
+  return dynamic(() => import('../components/world'))
+}
@@ -0,0 +1,13 @@
+const Inner = (()=>{
+    const dynamic = require('next/dynamic');
+    return dynamic(()=>import('../components/hello'), {
+        loadableGenerated: {
+            modules: [
+                "src/some-file.js -> " + "../components/hello"
+            ]
+        }
+    });
+})();
+function outer(dynamic) {
+    return dynamic(()=>import('../components/world'));
+}
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+const d = dynamic
+const e = d
+
+export const Hello = d(() => import('../components/hello'))
+export const World = e(() => import('../components/world'))
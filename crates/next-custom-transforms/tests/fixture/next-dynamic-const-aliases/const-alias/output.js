@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+const d = dynamic;
+const e = d;
+export const Hello = d(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+export const World = e(()=>import('../components/world'));
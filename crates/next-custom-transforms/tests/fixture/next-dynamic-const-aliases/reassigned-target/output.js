@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic';
+const d = dynamic;
+export function setDynamic(somethingElse) {
+    dynamic = somethingElse;
+}
+export const Hello = d(()=>import('../components/hello'));
@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "virtual/entry.js -> " + "../components/hello"
+        ]
+    }
+});
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const Widget = dynamic(() => import('../components/widget').then((mod) => mod.Widget))
+const Other = dynamic(async () => (await import('../components/widget')).Other)
+const Hello = dynamic(() => import('../components/hello'))
@@ -0,0 +1,22 @@
+import dynamic from 'next/dynamic';
+const Widget = dynamic(()=>import('../components/widget').then((mod)=>mod.Widget), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/widget#Widget"
+        ]
+    }
+});
+const Other = dynamic(async ()=>(await import('../components/widget')).Other, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/widget#Other"
+        ]
+    }
+});
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
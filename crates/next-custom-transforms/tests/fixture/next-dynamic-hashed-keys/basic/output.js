@@ -0,0 +1,16 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "3c77ce4a9a471be55f27c79693577e9b323db888"
+        ]
+    }
+});
+const World = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "81e5e54f50c33b801c9f7a28d8fcf3cea43766c1"
+        ]
+    },
+    ssr: false
+});
@@ -0,0 +1,12 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../components/hello" with {
+    "chunking-type": "none"
+};
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello",
+            id_src_some_file_js
+        ]
+    }
+});
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ],
+        module: "src/some-file.js -> " + "../components/hello"
+    }
+});
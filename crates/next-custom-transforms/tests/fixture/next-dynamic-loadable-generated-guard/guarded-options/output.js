@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), typeof __NEXT_LOADABLE__ !== "undefined" ? {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+} : void 0);
+const World = dynamic(()=>import('../components/world'), typeof __NEXT_LOADABLE__ !== "undefined" ? {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    },
+    ssr: false
+} : {
+    ssr: false
+});
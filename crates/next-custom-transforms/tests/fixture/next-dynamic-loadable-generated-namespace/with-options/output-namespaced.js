@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    __next: {
+        loadableGenerated: {
+            modules: [
+                "src/some-file.js -> " + "../components/hello"
+            ]
+        }
+    },
+    loading: ()=>null
+});
@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+
+const Hello = dynamic(() => import('../components/hello'))
+const World = dynamic(() => import('../components/world'), { ssr: false })
@@ -0,0 +1,18 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ],
+        ssr: true
+    }
+});
+const World = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ],
+        ssr: false
+    },
+    ssr: false
+});
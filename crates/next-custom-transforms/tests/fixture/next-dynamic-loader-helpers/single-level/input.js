@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const loadHello = () => import('../components/hello')
+
+const DynamicComponent = dynamic(() => loadHello())
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const loadHello = ()=>import('../components/hello');
+const DynamicComponent = dynamic(()=>loadHello(), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('./Hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/components/somefile.js -> " + "./Hello"
+        ]
+    }
+});
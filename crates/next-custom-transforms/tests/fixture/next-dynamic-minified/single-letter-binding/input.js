@@ -0,0 +1,17 @@
+import d from 'next/dynamic'
+
+const a = d(() => import('../components/hello'))
+
+function b(d) {
+  return d(() => import('../components/world'))
+}
+
+const c = () => {
+  const d = (e) => e
+  return [d(() => import('../components/first')), (() => {
+    const d = (f) => f
+    return d(() => import('../components/second'))
+  })()]
+}
+
+export const e = () => d(() => import('../components/other'))
@@ -0,0 +1,16 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello" + "?v=1a2b3c4d"
+        ]
+    }
+});
+const HelloAgain = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello" + "?v=1a2b3c4d"
+        ]
+    },
+    ssr: false
+});
@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "./components/hello"
+        ]
+    }
+});
+const World = dynamic(()=>import('./components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "./components/world"
+        ]
+    }
+});
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+
+const Prefetched = dynamic(() => import('../components/hello'), {
+  prefetch: true,
+})
+
+const NotPrefetched = dynamic(() => import('../components/world'), {
+  prefetch: false,
+})
@@ -0,0 +1,17 @@
+import dynamic from 'next/dynamic';
+const Prefetched = dynamic(()=>import(/* webpackPrefetch: true */ '../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    prefetch: true
+});
+const NotPrefetched = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    },
+    prefetch: false
+});
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const Prefetched = dynamic(() => import('../components/hello'), {
+  prefetch: true,
+  ssr: false,
+})
@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+const Prefetched = dynamic(async ()=>{
+    typeof require.resolveWeak !== "undefined" && require.resolveWeak("../components/hello");
+}, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    prefetch: true,
+    ssr: false
+});
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const Prefetched = dynamic(() => import(`../components/hello`), {
+  prefetch: true,
+})
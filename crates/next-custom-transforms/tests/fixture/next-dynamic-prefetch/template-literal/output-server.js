@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const Prefetched = dynamic(()=>import(/* webpackPrefetch: true */ `../components/hello`), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    prefetch: true
+});
@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(async ()=>{
+    typeof require.resolveWeak !== "undefined" && require.resolveWeak("../components/hello");
+}, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+});
@@ -0,0 +1,12 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../components/hello" with {
+    "chunking-type": "none"
+};
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    },
+    ssr: false
+});
@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+});
+export const __preload_hello = ()=>import("../components/hello");
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+import { __preload_world } from '../preload'
+
+export const __preload_hello = () => {}
+
+const Hello = dynamic(() => import('../components/hello'))
+const World = dynamic(() => import('../components/world'))
+
+export { __preload_world }
@@ -0,0 +1,20 @@
+import dynamic from 'next/dynamic';
+import { __preload_world } from '../preload';
+export const __preload_hello = ()=>{};
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+const World = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    }
+});
+export { __preload_world };
+export const __preload_hello_1 = ()=>import("../components/hello");
+export const __preload_world_1 = ()=>import("../components/world");
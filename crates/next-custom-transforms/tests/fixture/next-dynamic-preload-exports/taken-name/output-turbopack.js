@@ -0,0 +1,24 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../components/hello" with {
+    "chunking-type": "none"
+};
+import { __turbopack_module_id__ as id_src_some_file_js1 } from "../components/world" with {
+    "chunking-type": "none"
+};
+import dynamic from 'next/dynamic';
+import { __preload_world } from '../preload';
+export const __preload_hello = ()=>{};
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    }
+});
+const World = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js1
+        ]
+    }
+});
+export { __preload_world };
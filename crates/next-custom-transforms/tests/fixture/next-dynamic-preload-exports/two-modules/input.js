@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const Hello = dynamic(() => import('../components/hello'))
+const World = dynamic(() => import('../components/world.js'))
+const HelloAgain = dynamic(() => import('../components/hello'))
@@ -0,0 +1,31 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../components/hello" with {
+    "chunking-type": "none"
+};
+import { __turbopack_module_id__ as id_src_some_file_js1 } from "../components/world.js" with {
+    "chunking-type": "none"
+};
+import { __turbopack_module_id__ as id_src_some_file_js2 } from "../components/hello" with {
+    "chunking-type": "none"
+};
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    }
+});
+const World = dynamic(()=>import('../components/world.js'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js1
+        ]
+    }
+});
+const HelloAgain = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js2
+        ]
+    }
+});
@@ -0,0 +1,24 @@
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+const World = dynamic(()=>import('../components/world.js'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world.js"
+        ]
+    }
+});
+const HelloAgain = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+export const __preload_hello = ()=>import("../components/hello");
+export const __preload_world = ()=>import("../components/world.js");
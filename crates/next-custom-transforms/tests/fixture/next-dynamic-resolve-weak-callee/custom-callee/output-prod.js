@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        webpack: ()=>[
+                __custom_resolve_weak__("../components/hello")
+            ]
+    },
+    ssr: false
+});
@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(async ()=>{
+    typeof __custom_resolve_weak__ !== "undefined" && __custom_resolve_weak__("../components/hello");
+}, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+});
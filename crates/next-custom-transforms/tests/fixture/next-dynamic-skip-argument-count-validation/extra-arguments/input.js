@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(
+  () => import('../components/hello'),
+  { ssr: false },
+  { wrapper: true }
+)
+
+const NoArguments = dynamic()
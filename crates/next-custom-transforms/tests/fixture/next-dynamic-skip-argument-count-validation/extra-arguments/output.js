@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+}, {
+    wrapper: true
+});
+const NoArguments = dynamic();
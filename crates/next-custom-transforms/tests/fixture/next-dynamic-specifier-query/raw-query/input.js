@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic'
+
+const Raw = dynamic(() => import('../components/hello?raw'))
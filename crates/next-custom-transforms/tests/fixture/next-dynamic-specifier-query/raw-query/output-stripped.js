@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic';
+const Raw = dynamic(()=>import('../components/hello?raw'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
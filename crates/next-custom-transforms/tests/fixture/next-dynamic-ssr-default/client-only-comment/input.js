@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+export const ClientOnly = // next-dynamic: client-only
+  dynamic(() => import('../components/hello'))
+
+export const SameLine = /* next-dynamic: client-only */ dynamic(() => import('../components/world'))
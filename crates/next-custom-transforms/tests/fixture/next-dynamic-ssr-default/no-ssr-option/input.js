@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic'
+
+export const NoSsrOption = dynamic(() => import('../components/hello'))
+
+export const SsrTrue = dynamic(() => import('../components/hello'), {
+  ssr: true,
+})
+
+export const SsrFalse = dynamic(() => import('../components/hello'), {
+  ssr: false,
+})
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+export const SsrFalse = dynamic(() => import('../components/hello'), {
+  ssr: (false),
+})
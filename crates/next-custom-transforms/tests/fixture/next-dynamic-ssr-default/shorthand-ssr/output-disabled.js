@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+const ssr = false;
+export const SsrFalse = dynamic(async ()=>{
+    typeof require.resolveWeak !== "undefined" && require.resolveWeak("../components/hello");
+}, {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr
+});
@@ -0,0 +1,22 @@
+import { __custom_module_id__ as id_src_some_file_js } from "../components/hello" with {
+    "bundler-chunking-type": "none"
+};
+import { __custom_module_id__ as id_src_some_file_js1 } from "../components/world" with {
+    "bundler-chunking-type": "none"
+};
+import dynamic from 'next/dynamic';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    }
+});
+const World = dynamic(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js1
+        ]
+    },
+    ssr: false
+});
@@ -0,0 +1,16 @@
+import dynamic from 'next/dynamic';
+import track from '../lib/track';
+const Hello = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
+track(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    }
+});
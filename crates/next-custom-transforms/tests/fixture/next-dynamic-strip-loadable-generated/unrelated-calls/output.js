@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+import track from '../lib/track';
+const Hello = dynamic(()=>import('../components/hello'));
+track(()=>import('../components/world'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/world"
+        ]
+    }
+});
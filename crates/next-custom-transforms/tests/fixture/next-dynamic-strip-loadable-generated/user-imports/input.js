@@ -0,0 +1,11 @@
+import Hello from "../components/hello" with {
+    "bundler-transition": "next-dynamic"
+};
+import { __custom_module_id__ as helloId } from "../components/hello" with {
+    "bundler-chunking-type": "none"
+};
+import { __turbopack_module_id__ as id_world } from "../components/world" with {
+    "transition": "next-dynamic"
+};
+import dynamic from 'next/dynamic';
+const World = dynamic(()=>import('../components/world'));
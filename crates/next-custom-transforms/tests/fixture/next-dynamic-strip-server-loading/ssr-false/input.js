@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+import Spinner from '../components/spinner'
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  loading: () => <Spinner />,
+  ssr: false,
+})
@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+import Spinner from '../components/spinner';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    loading: ()=><Spinner/>,
+    ssr: false
+});
@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+import Spinner from '../components/spinner';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    },
+    ssr: false
+});
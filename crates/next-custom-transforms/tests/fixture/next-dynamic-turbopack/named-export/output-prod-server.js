@@ -0,0 +1,11 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../components/hello" with {
+    "transition": "next-dynamic"
+};
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(async ()=>(await import('../components/hello')).Named, {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    }
+});
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const DynamicWorker = dynamic(() =>
+  import('../workers/hello', { with: { type: 'worker' } })
+)
@@ -0,0 +1,16 @@
+import { __turbopack_module_id__ as id_src_some_file_js } from "../workers/hello" with {
+    "transition": "next-dynamic",
+    "type": "worker"
+};
+import dynamic from 'next/dynamic';
+const DynamicWorker = dynamic(()=>import('../workers/hello', {
+        with: {
+            type: 'worker'
+        }
+    }), {
+    loadableGenerated: {
+        modules: [
+            id_src_some_file_js
+        ]
+    }
+});
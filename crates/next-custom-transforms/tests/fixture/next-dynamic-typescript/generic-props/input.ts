@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+type Props = { name: string }
+
+const DynamicComponent = dynamic<Props>(() => import('../components/hello'))
@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+type Props = {
+    name: string;
+};
+const DynamicComponent = dynamic<Props>(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
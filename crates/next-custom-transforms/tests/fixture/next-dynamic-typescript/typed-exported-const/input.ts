@@ -0,0 +1,4 @@
+import dynamic from 'next/dynamic'
+import type React from 'react'
+
+export const DynamicComponent: React.ComponentType = dynamic(() => import('../components/hello'))
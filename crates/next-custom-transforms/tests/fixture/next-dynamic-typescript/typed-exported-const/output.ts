@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+import type React from 'react';
+export const DynamicComponent: React.ComponentType = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
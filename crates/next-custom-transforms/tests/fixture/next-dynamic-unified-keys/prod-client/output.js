@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/hello")
+            ],
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
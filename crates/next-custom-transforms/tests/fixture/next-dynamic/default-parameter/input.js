@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+export function Comp(C = dynamic(() => import('../components/hello'))) {
+  return C
+}
@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+export function Comp(C = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/hello")
+            ]
+    }
+})) {
+    return C;
+}
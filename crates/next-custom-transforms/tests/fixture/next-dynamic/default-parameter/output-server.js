@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+export function Comp(C = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+})) {
+    return C;
+}
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(async () => {
+  await somethingFirst()
+  return import('../components/hello')
+})
@@ -0,0 +1,3 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = (0, dynamic)(() => import('../components/hello'))
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = (0, dynamic)(()=>import('../components/hello')
+, {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/hello")
+            ]
+    }
+});
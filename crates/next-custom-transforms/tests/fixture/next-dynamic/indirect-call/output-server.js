@@ -0,0 +1,8 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = (0, dynamic)(()=>import('../components/hello'), {
+    loadableGenerated: {
+        modules: [
+            "src/some-file.js -> " + "../components/hello"
+        ]
+    }
+});
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+export default function Page() {
+  return <div>{dynamic(() => import('../components/hello'))}</div>
+}
@@ -0,0 +1,10 @@
+import dynamic from 'next/dynamic';
+export default function Page() {
+    return <div>{dynamic(()=>import('../components/hello'), {
+        loadableGenerated: {
+            modules: [
+                "src/some-file.js -> " + "../components/hello"
+            ]
+        }
+    })}</div>;
+}
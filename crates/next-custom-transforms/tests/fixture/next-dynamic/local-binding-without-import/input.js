@@ -0,0 +1,5 @@
+function dynamic(loader) {
+  return loader
+}
+
+const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,4 @@
+function dynamic(loader) {
+    return loader;
+}
+const DynamicComponent = dynamic(()=>import('../components/hello'));
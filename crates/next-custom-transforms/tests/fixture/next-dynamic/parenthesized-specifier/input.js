@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(() => import(('../components/hello')))
+
+const DeeplyParenthesized = dynamic(() => import(((`../components/world`))))
@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/hello")
+            ]
+    }
+});
+const DeeplyParenthesized = dynamic(()=>import(`../components/world`), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/world")
+            ]
+    }
+});
@@ -0,0 +1,6 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(() => import('../components/hello'), {
+  loadableGenerated: { chunkName: 'hello' },
+  ssr: true,
+})
@@ -0,0 +1,11 @@
+import dynamic from 'next/dynamic'
+
+export function setDynamic(loaders) {
+  [dynamic] = loaders
+}
+
+export function setDynamicFrom(loaders) {
+  ({ dynamic } = loaders)
+}
+
+export const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+export function bumpDynamic() {
+  dynamic++
+}
+
+export const DynamicComponent = dynamic(() => import('../components/hello'))
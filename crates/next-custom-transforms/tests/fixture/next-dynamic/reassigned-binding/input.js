@@ -0,0 +1,7 @@
+import dynamic from 'next/dynamic'
+
+export function setDynamic(somethingElse) {
+  dynamic = somethingElse
+}
+
+export const DynamicComponent = dynamic(() => import('../components/hello'))
@@ -0,0 +1,5 @@
+import dynamic from 'next/dynamic';
+export function setDynamic(somethingElse) {
+    dynamic = somethingElse;
+}
+export const DynamicComponent = dynamic(()=>import('../components/hello'));
@@ -0,0 +1,9 @@
+import dynamic from 'next/dynamic'
+
+const DynamicComponent = dynamic(() =>
+  import('../components/hello').then((mod) => mod.Hello)
+)
+
+const DynamicDefault = dynamic(() =>
+  import('../components/world').then((mod) => mod.default)
+)
@@ -0,0 +1,15 @@
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello').then((mod)=>mod.Hello), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/hello")
+            ]
+    }
+});
+const DynamicDefault = dynamic(()=>import('../components/world').then((mod)=>mod.default), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../components/world")
+            ]
+    }
+});
@@ -0,0 +1,5 @@
+'use no-loadable';
+import dynamic from 'next/dynamic';
+const DynamicComponent = dynamic(()=>import('../components/hello'), {
+    ssr: false
+});
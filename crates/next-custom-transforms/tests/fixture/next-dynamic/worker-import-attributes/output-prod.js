@@ -0,0 +1,12 @@
+import dynamic from 'next/dynamic';
+const DynamicWorker = dynamic(()=>import('../workers/hello', {
+        with: {
+            type: 'worker'
+        }
+    }), {
+    loadableGenerated: {
+        webpack: ()=>[
+                require.resolveWeak("../workers/hello")
+            ]
+    }
+});